@@ -1,8 +1,34 @@
 use optimizely::{decision::DecideOptions, Client};
+use std::alloc::{GlobalAlloc, Layout, System};
 use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 
 const FILE_PATH: &str = "../../datafiles/sandbox.json";
-const FLAG_KEY: &str = "buy_button";
+// The last flag key is not in the datafile, so it is decided with the off decision
+const FLAG_KEYS: [&str; 3] = ["buy_button", "qa_rollout", "unknown_flag"];
+const DECISIONS: usize = 1_000_000;
+
+// Allocator that counts the number of allocations and allocated bytes
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let client = Client::from_local_datafile(FILE_PATH)?.initialize();
@@ -13,10 +39,29 @@ fn main() -> Result<(), Box<dyn Error>> {
         ..DecideOptions::default()
     };
 
-    for i in 0..1_000_000 {
-        let user_id = format!("user{}", i);
-        let user_context = client.create_user_context(&user_id);
-        let _decision = user_context.decide_with_options(FLAG_KEY, &decide_options);
+    // Create the user IDs up front, so only the decisions are measured
+    let user_ids = (0..DECISIONS)
+        .map(|i| format!("user{}", i))
+        .collect::<Vec<_>>();
+
+    for flag_key in FLAG_KEYS {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+        let start = Instant::now();
+
+        for user_id in &user_ids {
+            let user_context = client.create_user_context(user_id);
+            let _decision = user_context.decide_with_options(flag_key, &decide_options);
+        }
+
+        let duration = start.elapsed();
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+        let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes;
+        println!(
+            "{flag_key}: {DECISIONS} decisions in {duration:?}, {:.2} allocations and {:.1} bytes per decision",
+            allocations as f64 / DECISIONS as f64,
+            allocated_bytes as f64 / DECISIONS as f64,
+        );
     }
 
     Ok(())
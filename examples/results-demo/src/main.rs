@@ -1,4 +1,4 @@
-use optimizely::{event_api::BatchedEventDispatcher, Client};
+use optimizely::{event_api::BatchedEventDispatcher, event_tags, Client};
 use rand::random;
 use std::collections::HashMap;
 use std::error::Error;
@@ -21,9 +21,9 @@ fn random_event_does_happen(chance: f32) -> bool {
     random::<f32>() < chance
 }
 
-/// Random revenue value between $0.00 and $655.35
-fn random_revenue() -> String {
-    random::<u16>().to_string()
+/// Random revenue value in cents between $0.00 and $655.35
+fn random_revenue() -> u16 {
+    random::<u16>()
 }
 
 fn random_category() -> &'static str {
@@ -67,14 +67,14 @@ fn main() -> Result<(), Box<dyn Error>> {
             if random_event_does_happen(conversion_rate) {
                 let category = random_category();
                 let properties = HashMap::from([(String::from("Category"), String::from(category))]);
-                let tags = HashMap::default();
+                let tags = event_tags! {};
 
                 user_context.track_event_with_properties_and_tags(ADD_TO_CART_EVENT_KEY, properties, tags);
 
                 // Purchase change stays equal, 30% change if someone adds something to cart
                 if random_event_does_happen(0.3) {
                     let properties = HashMap::from([(String::from("Category"), String::from(category))]);
-                    let tags = HashMap::from([(String::from("revenue"), random_revenue())]);
+                    let tags = event_tags! { "revenue" => random_revenue() };
 
                     user_context.track_event_with_properties_and_tags(PURCHASE_EVENT_KEY, properties, tags);
 
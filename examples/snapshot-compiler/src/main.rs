@@ -0,0 +1,41 @@
+use optimizely::datafile::Datafile;
+use std::error::Error;
+use std::time::Instant;
+
+const USAGE: &str = "Usage: snapshot-compiler <datafile.json> <snapshot.bin>";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Read input and output path from the command line
+    let mut args = std::env::args().skip(1);
+    let (Some(input_path), Some(output_path)) = (args.next(), args.next()) else {
+        eprintln!("{USAGE}");
+        std::process::exit(2);
+    };
+
+    // Parse and validate the JSON datafile
+    let content = std::fs::read(&input_path)?;
+    let datafile = Datafile::from_slice(&content)?;
+
+    // Write the compiled snapshot
+    let snapshot = datafile.to_snapshot()?;
+    std::fs::write(&output_path, &snapshot)?;
+    println!(
+        "Compiled revision {} from {} bytes of JSON into {} bytes",
+        datafile.revision(),
+        content.len(),
+        snapshot.len()
+    );
+
+    // Compare the time to load the JSON datafile and the snapshot
+    let start = Instant::now();
+    Datafile::from_slice(&content)?;
+    let json_duration = start.elapsed();
+
+    let start = Instant::now();
+    Datafile::from_snapshot(&snapshot)?;
+    let snapshot_duration = start.elapsed();
+
+    println!("Loading JSON took {json_duration:?}, loading the snapshot took {snapshot_duration:?}");
+
+    Ok(())
+}
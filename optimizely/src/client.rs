@@ -1,21 +1,47 @@
 //! Entrypoint of the SDK
 
+// External imports
+use std::sync::{Arc, PoisonError, RwLock};
+
 // Imports from crate
+use crate::config::OptimizelyConfig;
 use crate::datafile::Datafile;
+use crate::decision::DecideOptions;
 #[cfg(feature = "online")]
+use crate::event_api::EventApiClient;
 use crate::event_api::EventDispatcher;
+#[cfg(feature = "online")]
+use crate::http::HttpClient;
+use crate::notification::NotificationCenter;
 
 // Relative imports of sub modules
 pub use error::ClientError;
+#[cfg(feature = "online")]
+pub use fetcher::DatafileFetcher;
 pub use initialization::UninitializedClient;
-pub use user::{UserAttributes, UserContext};
+pub use source::DatafileSource;
+pub use track_error::TrackError;
+pub use user::{OwnedUserContext, UserAttributes, UserContext};
 
+#[cfg(feature = "online")]
+mod cache;
 mod error;
+#[cfg(feature = "online")]
+mod fetcher;
 mod initialization;
+#[cfg(feature = "online")]
+mod poller;
+mod source;
+mod track_error;
 mod user;
 
+/// Shared location of the datafile, so it can be swapped while the client is in use
+pub(crate) type DatafileSlot = Arc<RwLock<Arc<Datafile>>>;
+
 /// SDK client to use Optimizely Feature Experimentation
 ///
+/// The client is `Send` and `Sync`, so a single client can be shared between threads using an `Arc`.
+///
 /// ```
 /// use optimizely::Client;
 /// #
@@ -27,21 +53,29 @@ mod user;
 ///     .initialize();
 ///
 /// // Use methods of client struct
-/// let account_id = optimizely_client.datafile().account_id();
-/// let revision = optimizely_client.datafile().revision();
+/// let datafile = optimizely_client.datafile();
+/// let account_id = datafile.account_id();
+/// let revision = datafile.revision();
 /// let user_context = optimizely_client.create_user_context(user_id);
 ///
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub struct Client {
-    datafile: Datafile,
+    datafile: DatafileSlot,
+    notification_center: Arc<NotificationCenter>,
+    default_decide_options: DecideOptions,
+    #[cfg(feature = "online")]
+    _poller: Option<poller::DatafilePoller>,
+    event_dispatcher: Option<Box<dyn EventDispatcher>>,
     #[cfg(feature = "online")]
-    event_dispatcher: Box<dyn EventDispatcher>,
+    event_endpoint: Option<String>,
+    #[cfg(feature = "online")]
+    http_client: Arc<dyn HttpClient>,
 }
 
 impl Client {
     /// Create a new user context for a given user id
-    pub fn create_user_context<'a>(&'a self, user_id: &'a str) -> UserContext {
+    pub fn create_user_context<'a>(&'a self, user_id: &'a str) -> UserContext<'a> {
         // Create an empty set of user attributes
         let attributes = UserAttributes::new();
 
@@ -51,18 +85,86 @@ impl Client {
     /// Create a new user context for a given user id
     pub fn create_user_context_with_attributes<'a>(
         &'a self, user_id: &'a str, attributes: UserAttributes,
-    ) -> UserContext {
+    ) -> UserContext<'a> {
         UserContext::new(self, user_id, attributes)
     }
 
-    /// Get the datafile within the client
-    pub fn datafile(&self) -> &Datafile {
-        &self.datafile
+    /// Create a new user context for a given user id that does not borrow from the client
+    pub fn create_owned_user_context<T: Into<String>>(self: &Arc<Self>, user_id: T) -> OwnedUserContext {
+        // Create an empty set of user attributes
+        let attributes = UserAttributes::new();
+
+        UserContext::new_owned(Arc::clone(self), user_id.into(), attributes)
+    }
+
+    /// Create a new user context for a given user id that does not borrow from the client
+    pub fn create_owned_user_context_with_attributes<T: Into<String>>(
+        self: &Arc<Self>, user_id: T, attributes: UserAttributes,
+    ) -> OwnedUserContext {
+        UserContext::new_owned(Arc::clone(self), user_id.into(), attributes)
+    }
+
+    /// Get the latest datafile within the client
+    ///
+    /// The returned datafile is a snapshot, it is not affected when the client receives a new datafile.
+    pub fn datafile(&self) -> Arc<Datafile> {
+        let datafile = self.datafile.read().unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(&datafile)
+    }
+
+    /// Get a read-only view of the latest datafile
+    pub fn optimizely_config(&self) -> OptimizelyConfig {
+        self.datafile().optimizely_config()
+    }
+
+    /// Get the notification center to add and remove listeners
+    pub fn notification_center(&self) -> &NotificationCenter {
+        &self.notification_center
+    }
+
+    /// Get the default options that are combined with the options of every decision
+    pub fn default_decide_options(&self) -> &DecideOptions {
+        &self.default_decide_options
     }
 
     /// Get the event dispatcher within the client
+    ///
+    /// Unless a custom event dispatcher was specified, this is the simple event dispatcher.
     #[cfg(feature = "online")]
     pub fn event_dispatcher(&self) -> &dyn EventDispatcher {
-        &*self.event_dispatcher
+        self.optional_event_dispatcher()
+            .expect("client falls back to the simple event dispatcher")
+    }
+
+    /// Get the event dispatcher within the client, if there is one
+    ///
+    /// Without the `online` feature, there is only an event dispatcher if a custom one was specified.
+    pub fn optional_event_dispatcher(&self) -> Option<&dyn EventDispatcher> {
+        self.event_dispatcher.as_deref()
+    }
+
+    /// Get the Event API endpoint to which events are sent
+    ///
+    /// Unless a custom endpoint was specified, it is derived from the region in the datafile.
+    #[cfg(feature = "online")]
+    pub fn event_endpoint(&self) -> &str {
+        match &self.event_endpoint {
+            Some(endpoint) => endpoint,
+            None => EventApiClient::region_endpoint(self.datafile().region()),
+        }
+    }
+
+    /// Get the HTTP client used to send events
+    #[cfg(feature = "online")]
+    pub fn http_client(&self) -> &dyn HttpClient {
+        &*self.http_client
+    }
+
+    /// Get a client for the Event API using the endpoint and HTTP client of this client
+    #[cfg(feature = "online")]
+    pub fn event_api_client(&self) -> EventApiClient {
+        EventApiClient::new(self.event_endpoint())
+            .with_http_client(Arc::clone(&self.http_client))
+            .with_notification_center(Arc::clone(&self.notification_center))
     }
 }
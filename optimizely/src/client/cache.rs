@@ -0,0 +1,163 @@
+// External imports
+use error_stack::{report, Result, ResultExt};
+use fasthash::murmur3::hash32 as murmur3_hash;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Imports from crate
+use crate::client::ClientError;
+use crate::datafile::Datafile;
+
+// Counter that makes the temporary files of a process unique, together with the process ID
+static TEMPORARY_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Maximum age of a cached datafile, unless a different maximum age is specified
+pub(crate) const DEFAULT_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Cache file that stores the last good datafile of an SDK key
+///
+/// Next to the datafile itself, the file contains the revision, the validators of the HTTP response,
+/// the time at which it was stored and a checksum.
+/// Files that cannot be parsed, of which the checksum or revision does not match, or that are too old, are ignored.
+pub(crate) struct DatafileCache {
+    path: PathBuf,
+}
+
+/// Content of the cache file
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CacheEntry {
+    pub(crate) revision: u32,
+    pub(crate) etag: Option<String>,
+    #[serde(rename = "lastModified")]
+    pub(crate) last_modified: Option<String>,
+    // Seconds since the Unix epoch
+    #[serde(rename = "storedAt")]
+    stored_at: u64,
+    checksum: u32,
+    datafile: String,
+}
+
+impl DatafileCache {
+    /// Use a cache file for the SDK key in the given directory
+    ///
+    /// Characters other than ASCII letters, digits, `-` and `_` are escaped,
+    /// so an SDK key can not refer to a file outside the cache directory.
+    pub(crate) fn new(cache_dir: &Path, sdk_key: &str) -> DatafileCache {
+        DatafileCache {
+            path: cache_dir.join(format!("{}.json", escape_file_name(sdk_key))),
+        }
+    }
+
+    /// Use a cache file at the given path
+    pub(crate) fn from_path(path: PathBuf) -> DatafileCache {
+        DatafileCache { path }
+    }
+
+    /// Get the path of the cache file
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the cached datafile, fails if there is no valid cache file that is not older than the maximum age
+    pub(crate) fn load(&self, max_age: Duration) -> Result<(Datafile, CacheEntry), ClientError> {
+        let content = fs::read_to_string(&self.path)
+            .change_context(ClientError::FailedFileRead)
+            .attach_printable_lazy(|| format!("Cache file: {}", self.path.display()))?;
+
+        // The cache file might be truncated or modified, so every step is verified
+        let entry = serde_json::from_str::<CacheEntry>(&content)
+            .change_context(ClientError::FailedFileRead)
+            .attach_printable("Cache file is corrupt")
+            .attach_printable_lazy(|| format!("Cache file: {}", self.path.display()))?;
+
+        if murmur3_hash(entry.datafile.as_bytes()) != entry.checksum {
+            return Err(report!(ClientError::FailedFileRead))
+                .attach_printable("Checksum of the cached datafile does not match")
+                .attach_printable_lazy(|| format!("Cache file: {}", self.path.display()));
+        }
+
+        // A stored time in the future is treated as just stored
+        let stored_at = UNIX_EPOCH + Duration::from_secs(entry.stored_at);
+        let age = SystemTime::now()
+            .duration_since(stored_at)
+            .unwrap_or_default();
+        if age > max_age {
+            return Err(report!(ClientError::FailedFileRead))
+                .attach_printable(format!("Cached datafile is {} seconds old", age.as_secs()))
+                .attach_printable_lazy(|| format!("Cache file: {}", self.path.display()));
+        }
+
+        let datafile = Datafile::build(&entry.datafile)
+            .change_context(ClientError::InvalidDatafile)
+            .attach_printable_lazy(|| format!("Cache file: {}", self.path.display()))?;
+
+        if datafile.revision() != entry.revision {
+            return Err(report!(ClientError::FailedFileRead))
+                .attach_printable(format!(
+                    "Revision {} of the cached datafile does not match revision {} of the cache file",
+                    datafile.revision(),
+                    entry.revision
+                ))
+                .attach_printable_lazy(|| format!("Cache file: {}", self.path.display()));
+        }
+
+        log::info!("Loaded cached datafile with revision {}", entry.revision);
+        Ok((datafile, entry))
+    }
+
+    /// Store a datafile that was downloaded and parsed successfully
+    pub(crate) fn store(
+        &self, datafile: &str, revision: u32, etag: Option<&str>, last_modified: Option<&str>,
+    ) -> Result<(), ClientError> {
+        let entry = CacheEntry {
+            revision,
+            etag: etag.map(String::from),
+            last_modified: last_modified.map(String::from),
+            stored_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            checksum: murmur3_hash(datafile.as_bytes()),
+            datafile: datafile.into(),
+        };
+        let content = serde_json::to_string(&entry).change_context(ClientError::FailedCacheWrite)?;
+
+        // Create the cache directory if needed
+        if let Some(cache_dir) = self.path.parent() {
+            fs::create_dir_all(cache_dir)
+                .change_context(ClientError::FailedCacheWrite)
+                .attach_printable_lazy(|| format!("Cache directory: {}", cache_dir.display()))?;
+        }
+
+        // Write to a temporary file first, so other processes never read a partially written file
+        // Every writer uses its own temporary file, so concurrent writers do not overwrite each other's file
+        let counter = TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let temporary_path = self
+            .path
+            .with_extension(format!("json.{}.{counter}.tmp", std::process::id()));
+        fs::write(&temporary_path, content)
+            .and_then(|()| fs::rename(&temporary_path, &self.path))
+            .inspect_err(|_| drop(fs::remove_file(&temporary_path)))
+            .change_context(ClientError::FailedCacheWrite)
+            .attach_printable_lazy(|| format!("Cache file: {}", self.path.display()))?;
+
+        log::debug!("Stored datafile with revision {revision} in cache");
+        Ok(())
+    }
+}
+
+/// Escape every byte that is not an ASCII letter, digit, `-` or `_` as `%XX`
+fn escape_file_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            escaped.push(char::from(byte));
+        } else {
+            escaped.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    escaped
+}
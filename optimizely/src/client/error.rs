@@ -22,4 +22,10 @@ pub enum ClientError {
     #[doc(hidden)]
     #[error("Invalid Datafile")]
     InvalidDatafile,
+    #[doc(hidden)]
+    #[error("Failed to load the datafile from any of the sources")]
+    AllSourcesFailed,
+    #[doc(hidden)]
+    #[error("Failed to write datafile cache")]
+    FailedCacheWrite,
 }
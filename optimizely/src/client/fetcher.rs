@@ -0,0 +1,367 @@
+// External imports
+use error_stack::{report, Result, ResultExt};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+// Imports from crate
+use crate::client::cache::{DatafileCache, DEFAULT_MAX_AGE};
+use crate::client::ClientError;
+use crate::datafile::Datafile;
+use crate::http::{HttpClient, HttpError, HttpResponse, UreqHttpClient};
+
+// URL templates of the CDN, the placeholder is replaced by the SDK key
+const PUBLIC_URL_TEMPLATE: &str = "https://cdn.optimizely.com/datafiles/{sdk_key}.json";
+const AUTHENTICATED_URL_TEMPLATE: &str = "https://config.optimizely.com/datafiles/auth/{sdk_key}.json";
+const SDK_KEY_PLACEHOLDER: &str = "{sdk_key}";
+
+// Status code for a conditional request of which the content has not changed
+const NOT_MODIFIED_STATUS: u16 = 304;
+
+// Time to wait for the first download before starting from the cached datafile
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Downloads the datafile from the CDN using an SDK key
+///
+/// The fetcher remembers the `ETag` and `Last-Modified` headers of the last response,
+/// so subsequent requests only download the datafile when it has changed.
+///
+/// ```no_run
+/// use optimizely::{client::DatafileFetcher, http::UreqHttpClient, Client};
+///
+/// // Download the datafile using a specific HTTP client
+/// let fetcher = DatafileFetcher::new("KVpGWnzPGKvvQ8yeEWmJZ")
+///     .with_http_client(UreqHttpClient::default());
+/// let optimizely_client = Client::from_fetcher(fetcher)?
+///     .initialize();
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// Datafiles of secure environments are downloaded from an authenticated endpoint using an access token.
+/// Only a fetcher can be configured with an access token, so use [`Client::from_fetcher`](crate::Client::from_fetcher) instead of an SDK key.
+///
+/// ```no_run
+/// use optimizely::{client::DatafileFetcher, Client};
+///
+/// // Download the datafile of a secure environment
+/// let fetcher = DatafileFetcher::new("KVpGWnzPGKvvQ8yeEWmJZ")
+///     .with_access_token("datafile-access-token");
+/// let optimizely_client = Client::from_fetcher(fetcher)?
+///     .initialize();
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// The last good datafile can be stored in a cache directory, so the client can start without the network.
+///
+/// ```no_run
+/// use optimizely::{client::DatafileFetcher, Client};
+/// use std::time::Duration;
+///
+/// // Fall back to the cached datafile when the CDN can not be reached within 5 seconds, and refresh it by polling
+/// let fetcher = DatafileFetcher::new("KVpGWnzPGKvvQ8yeEWmJZ")
+///     .with_cache_dir("/var/cache/optimizely");
+/// let optimizely_client = Client::from_fetcher(fetcher)?
+///     .with_polling_interval(Duration::from_secs(60))
+///     .initialize();
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct DatafileFetcher {
+    sdk_key: String,
+    http_client: Arc<dyn HttpClient>,
+    access_token: Option<String>,
+    url_template: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    revision: Option<u32>,
+    cache: Option<DatafileCache>,
+    cache_max_age: Duration,
+    startup_timeout: Duration,
+}
+
+impl DatafileFetcher {
+    /// Construct a new fetcher for the given SDK key
+    pub fn new<T: Into<String>>(sdk_key: T) -> DatafileFetcher {
+        DatafileFetcher {
+            sdk_key: sdk_key.into(),
+            http_client: Arc::new(UreqHttpClient::default()),
+            access_token: None,
+            url_template: None,
+            etag: None,
+            last_modified: None,
+            revision: None,
+            cache: None,
+            cache_max_age: DEFAULT_MAX_AGE,
+            startup_timeout: DEFAULT_STARTUP_TIMEOUT,
+        }
+    }
+
+    /// Use a custom HTTP client to download the datafile
+    pub fn with_http_client(mut self, http_client: impl HttpClient + 'static) -> DatafileFetcher {
+        self.http_client = Arc::new(http_client);
+        self
+    }
+
+    /// Use a datafile access token to download the datafile of a secure environment
+    ///
+    /// The token is sent as `Bearer` token in the `Authorization` header.
+    /// Unless a custom URL template is specified, the authenticated endpoint is used.
+    pub fn with_access_token<T: Into<String>>(mut self, access_token: T) -> DatafileFetcher {
+        self.access_token = Some(access_token.into());
+        self
+    }
+
+    /// Use a custom URL template to download the datafile
+    ///
+    /// The placeholder `{sdk_key}` in the template is replaced by the SDK key,
+    /// for example `https://cdn.example.com/datafiles/{sdk_key}.json`.
+    pub fn with_url_template<T: Into<String>>(mut self, url_template: T) -> DatafileFetcher {
+        self.url_template = Some(url_template.into());
+        self
+    }
+
+    /// Store the last good datafile in a cache directory
+    ///
+    /// The client always tries to download the datafile first.
+    /// When the CDN can not be reached, or does not respond within the startup timeout,
+    /// the client starts from the cached datafile instead, as long as it is not older than the maximum age.
+    /// Enable polling to replace the cached datafile once the CDN is reachable again.
+    /// Corrupt cache files are ignored and replaced by the next downloaded datafile.
+    pub fn with_cache_dir<T: Into<PathBuf>>(mut self, cache_dir: T) -> DatafileFetcher {
+        self.cache = Some(DatafileCache::new(&cache_dir.into(), &self.sdk_key));
+        self
+    }
+
+    /// Ignore cached datafiles that are older than the given maximum age, which is a week by default
+    pub fn with_cache_max_age(mut self, max_age: Duration) -> DatafileFetcher {
+        self.cache_max_age = max_age;
+        self
+    }
+
+    /// Start from the cached datafile when the first download takes longer than the given timeout
+    ///
+    /// The timeout is 5 seconds by default and only applies when a cache directory is used,
+    /// since without a cached datafile the client can only wait for the download.
+    pub fn with_startup_timeout(mut self, startup_timeout: Duration) -> DatafileFetcher {
+        self.startup_timeout = startup_timeout;
+        self
+    }
+
+    /// Get the SDK key
+    pub fn sdk_key(&self) -> &str {
+        &self.sdk_key
+    }
+
+    /// Get the URL from which the datafile is downloaded
+    pub fn url(&self) -> String {
+        // Select the default template depending on the access token
+        let url_template = match (&self.url_template, &self.access_token) {
+            (Some(url_template), _) => url_template,
+            (None, Some(_)) => AUTHENTICATED_URL_TEMPLATE,
+            (None, None) => PUBLIC_URL_TEMPLATE,
+        };
+
+        url_template.replace(SDK_KEY_PLACEHOLDER, &self.sdk_key)
+    }
+
+    /// Get the `ETag` header of the last downloaded datafile
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Get the `Last-Modified` header of the last downloaded datafile
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
+    /// Get the path of the cache file, if a cache directory is used
+    pub fn cache_path(&self) -> Option<&Path> {
+        self.cache.as_ref().map(DatafileCache::path)
+    }
+
+    /// Get the HTTP client used by this fetcher
+    pub(crate) fn http_client(&self) -> Arc<dyn HttpClient> {
+        Arc::clone(&self.http_client)
+    }
+
+    /// Load the datafile from the cache directory, returns `None` if there is no valid cached datafile
+    ///
+    /// The validators of the cached datafile are used for the next conditional request.
+    pub fn load_cached(&mut self) -> Option<Datafile> {
+        let (datafile, entry) = match self.cache.as_ref()?.load(self.cache_max_age) {
+            Ok(loaded) => loaded,
+            Err(report) => {
+                log::warn!("Ignoring datafile cache");
+                log::warn!("\n{report:?}");
+                return None;
+            }
+        };
+
+        // Continue as if the cached datafile was just downloaded
+        self.etag = entry.etag;
+        self.last_modified = entry.last_modified;
+        self.revision = Some(entry.revision);
+
+        Some(datafile)
+    }
+
+    /// Download and parse the datafile
+    pub fn fetch(&mut self) -> Result<Datafile, ClientError> {
+        // Make unconditional GET request
+        let response = self.request(Vec::new(), None)?;
+
+        // Use response body to build Datafile
+        self.build(response.body())
+    }
+
+    /// Download the datafile when starting the client, or load the cached datafile if that fails
+    ///
+    /// With a cache directory, the download gives up after the startup timeout,
+    /// so an unreachable network does not delay starting from the cached datafile.
+    pub(crate) fn fetch_or_load_cached(&mut self) -> Result<Datafile, ClientError> {
+        if self.cache.is_none() {
+            return self.fetch();
+        }
+
+        let result = self
+            .request(Vec::new(), Some(self.startup_timeout))
+            .and_then(|response| self.build(response.body()));
+
+        result.or_else(|report| match self.load_cached() {
+            Some(datafile) => {
+                log::warn!("Failed to download datafile, using cached datafile instead");
+                log::warn!("\n{report:?}");
+                Ok(datafile)
+            }
+            None => Err(report),
+        })
+    }
+
+    /// Download and parse the datafile, unless it has not changed since the last request
+    ///
+    /// Returns `None` when the CDN responds with `304 Not Modified`,
+    /// or when the downloaded datafile has the same revision as the last one.
+    pub fn fetch_if_changed(&mut self) -> Result<Option<Datafile>, ClientError> {
+        // Add the validators of the last response
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match", etag.clone()));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(("If-Modified-Since", last_modified.clone()));
+        }
+
+        // Make conditional GET request
+        let response = self.request(headers, None)?;
+        if response.status() == NOT_MODIFIED_STATUS {
+            log::debug!("Datafile has not been modified");
+            return Ok(None);
+        }
+
+        // Parsing only the revision is cheaper than building the whole Datafile
+        let revision = Datafile::parse_revision(response.body()).change_context(ClientError::InvalidDatafile)?;
+        if self.revision == Some(revision) {
+            log::debug!("Datafile revision has not changed");
+            return Ok(None);
+        }
+
+        // Use response body to build Datafile
+        self.build(response.body()).map(Some)
+    }
+
+    fn request(
+        &mut self, mut headers: Vec<(&str, String)>, timeout: Option<Duration>,
+    ) -> Result<HttpResponse, ClientError> {
+        // Only conditional requests can be answered with not modified
+        let is_conditional = !headers.is_empty();
+
+        // Authenticate using the access token
+        if let Some(access_token) = &self.access_token {
+            headers.push(("Authorization", format!("Bearer {access_token}")));
+        }
+
+        // Make GET request
+        let response = match timeout {
+            Some(timeout) => self.get_with_timeout(headers, timeout)?,
+            None => self
+                .get(&headers)
+                .change_context(ClientError::FailedRequest)?,
+        };
+
+        // A not modified response does not contain new validators
+        if response.status() == NOT_MODIFIED_STATUS && is_conditional {
+            return Ok(response);
+        }
+
+        // Only a successful response contains a datafile
+        if !response.is_success() {
+            return Err(report!(ClientError::FailedRequest))
+                .attach_printable(format!("Unexpected status code {}", response.status()));
+        }
+
+        // Remember the validators for the next request
+        self.etag = response.header("ETag").map(String::from);
+        self.last_modified = response.header("Last-Modified").map(String::from);
+
+        Ok(response)
+    }
+
+    fn get(&self, headers: &[(&str, String)]) -> Result<HttpResponse, HttpError> {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect::<Vec<_>>();
+
+        self.http_client.get(&self.url(), &headers)
+    }
+
+    /// Make the GET request in a separate thread, which is abandoned when it takes longer than the timeout
+    ///
+    /// The HTTP client can not be interrupted, so the thread finishes the request in the background.
+    fn get_with_timeout(&self, headers: Vec<(&str, String)>, timeout: Duration) -> Result<HttpResponse, ClientError> {
+        let (transmitter, receiver) = mpsc::channel();
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value))
+            .collect::<Vec<_>>();
+        let http_client = Arc::clone(&self.http_client);
+        let url = self.url();
+
+        thread::spawn(move || {
+            let headers = headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect::<Vec<_>>();
+
+            // The receiver is gone when the request took too long
+            drop(transmitter.send(http_client.get(&url, &headers)));
+        });
+
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => result.change_context(ClientError::FailedRequest),
+            Err(_) => Err(report!(ClientError::FailedRequest))
+                .attach_printable(format!("No response within the startup timeout of {timeout:?}")),
+        }
+    }
+
+    fn build(&mut self, content: &str) -> Result<Datafile, ClientError> {
+        let datafile = Datafile::build(content).change_context(ClientError::InvalidDatafile)?;
+
+        // Remember the revision for the next request
+        self.revision = Some(datafile.revision());
+
+        // Failing to write the cache should not stop the client from using the datafile
+        if let Some(cache) = &self.cache {
+            let result = cache.store(content, datafile.revision(), self.etag(), self.last_modified());
+            if let Err(report) = result {
+                log::warn!("Failed to store datafile in cache");
+                log::warn!("\n{report:?}");
+            }
+        }
+
+        Ok(datafile)
+    }
+}
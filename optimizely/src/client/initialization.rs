@@ -1,14 +1,29 @@
 // External imports
-use error_stack::{Result, ResultExt};
+use error_stack::{report, Report, Result, ResultExt};
+use serde_json::Value;
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufReader, Read};
+#[cfg(feature = "online")]
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "online")]
+use std::time::Duration;
 
 // Imports from crate
-use crate::client::{Client, ClientError};
-use crate::datafile::Datafile;
+use crate::client::{Client, ClientError, DatafileSource};
+use crate::datafile::{Datafile, DatafileError};
+use crate::decision::DecideOptions;
+use crate::notification::NotificationCenter;
 
 #[cfg(feature = "online")]
-use crate::event_api::{EventDispatcher, SimpleEventDispatcher};
+use crate::client::cache::{DatafileCache, DEFAULT_MAX_AGE};
+#[cfg(feature = "online")]
+use crate::client::{poller::DatafilePoller, DatafileFetcher};
+use crate::event_api::EventDispatcher;
+#[cfg(feature = "online")]
+use crate::event_api::SimpleEventDispatcher;
+#[cfg(feature = "online")]
+use crate::http::{HttpClient, UreqHttpClient};
 
 /// An intermediate struct that is returned when building a new Client
 ///
@@ -27,51 +42,128 @@ use crate::event_api::{EventDispatcher, SimpleEventDispatcher};
 /// ```
 pub struct UninitializedClient {
     datafile: Datafile,
-    _default_decide_options: Option<()>,
+    default_decide_options: DecideOptions,
     _user_profile_service: Option<()>,
-    #[cfg(feature = "online")]
     event_dispatcher: Option<Box<dyn EventDispatcher>>,
+    #[cfg(feature = "online")]
+    event_endpoint: Option<String>,
+    #[cfg(feature = "online")]
+    http_client: Option<Arc<dyn HttpClient>>,
+    #[cfg(feature = "online")]
+    fetcher: Option<DatafileFetcher>,
+    #[cfg(feature = "online")]
+    polling_interval: Option<Duration>,
+    #[cfg(feature = "async")]
+    async_polling: bool,
 }
 
 impl Client {
     /// Download the datafile from the CDN using an SDK key
+    ///
+    /// Secure environments and custom CDNs are not supported with only an SDK key.
+    /// Use [`Client::from_fetcher`] with [`DatafileFetcher::with_access_token`] or
+    /// [`DatafileFetcher::with_url_template`] instead.
     #[cfg(feature = "online")]
     pub fn from_sdk_key(sdk_key: &str) -> Result<UninitializedClient, ClientError> {
-        // Construct URL
-        let url = format!("https://cdn.optimizely.com/datafiles/{}.json", sdk_key);
+        // Use the default fetcher for the SDK key
+        Client::from_fetcher(DatafileFetcher::new(sdk_key))
+    }
 
-        // Make GET request
-        // TODO: implement polling mechanism
-        let response = ureq::get(&url)
-            .call()
-            .change_context(ClientError::FailedRequest)?;
+    /// Download the datafile from the CDN using a configured fetcher
+    ///
+    /// This is the supported way to download the datafile of a secure environment with an access token,
+    /// or to download it from a custom URL template.
+    /// The HTTP client of the fetcher is also used to send events.
+    /// If the fetcher uses a cache directory, the cached datafile is used when the download fails
+    /// or does not finish within the startup timeout of the fetcher.
+    #[cfg(feature = "online")]
+    pub fn from_fetcher(fetcher: DatafileFetcher) -> Result<UninitializedClient, ClientError> {
+        Client::from_fetcher_or_keep(fetcher, &mut None)
+    }
 
-        // Get response body
-        let content = response
-            .into_string()
-            .change_context(ClientError::FailedResponse)?;
+    /// Download the datafile from the CDN using an SDK key, without blocking the async runtime
+    #[cfg(feature = "async")]
+    pub async fn from_sdk_key_async(sdk_key: &str) -> Result<UninitializedClient, ClientError> {
+        // Use the default fetcher for the SDK key
+        Client::from_fetcher_async(DatafileFetcher::new(sdk_key)).await
+    }
 
-        // Use response to build Client
-        Client::from_string(&content)
+    /// Download the datafile from the CDN using a configured fetcher, without blocking the async runtime
+    ///
+    /// The HTTP client is blocking, so the request is made on a thread where blocking is acceptable.
+    #[cfg(feature = "async")]
+    pub async fn from_fetcher_async(fetcher: DatafileFetcher) -> Result<UninitializedClient, ClientError> {
+        tokio::task::spawn_blocking(move || Client::from_fetcher(fetcher))
+            .await
+            .change_context(ClientError::FailedRequest)?
     }
 
     /// Read the datafile from the local filesystem
     pub fn from_local_datafile(file_path: &str) -> Result<UninitializedClient, ClientError> {
-        // Read content from local path
-        let mut content = String::new();
-
         // Open file
-        let mut file = File::open(file_path).change_context(ClientError::FailedFileOpen)?;
-
-        // Read file content into String
-        file.read_to_string(&mut content)
-            .change_context(ClientError::FailedFileRead)?;
+        let file = File::open(file_path).change_context(ClientError::FailedFileOpen)?;
 
         // Use file content to build Client
-        Client::from_string(&content)
+        Client::from_reader(BufReader::new(file))
+    }
+
+    /// Read the datafile from a reader, like a file or the body of a downloaded object
+    ///
+    /// The datafile is parsed while reading, so unbuffered readers should be wrapped in a `BufReader`.
+    pub fn from_reader(reader: impl Read) -> Result<UninitializedClient, ClientError> {
+        // Create datafile while reading
+        let datafile = Datafile::from_reader(reader).map_err(|report| {
+            let context = match report.current_context() {
+                DatafileError::FailedRead => ClientError::FailedFileRead,
+                _ => ClientError::InvalidDatafile,
+            };
+            report.change_context(context)
+        })?;
+
+        // Return uninitialized client
+        Ok(UninitializedClient::new(datafile))
+    }
+
+    /// Use bytes containing a JSON document as the datafile
+    pub fn from_bytes(content: &[u8]) -> Result<UninitializedClient, ClientError> {
+        // Create datafile from bytes
+        let datafile = Datafile::from_slice(content).change_context(ClientError::InvalidDatafile)?;
+
+        // Return uninitialized client
+        Ok(UninitializedClient::new(datafile))
+    }
+
+    /// Use an already parsed JSON document as the datafile, like a value from a configuration system
+    pub fn from_value(content: Value) -> Result<UninitializedClient, ClientError> {
+        // Create datafile from a JSON value
+        let datafile = Datafile::from_value(content).change_context(ClientError::InvalidDatafile)?;
+
+        // Return uninitialized client
+        Ok(UninitializedClient::new(datafile))
+    }
+
+    /// Load the datafile from a binary snapshot, which is faster than parsing JSON
+    #[cfg(feature = "snapshot")]
+    pub fn from_snapshot(snapshot: &[u8]) -> Result<UninitializedClient, ClientError> {
+        // Create datafile from a snapshot
+        let datafile = Datafile::from_snapshot(snapshot).change_context(ClientError::InvalidDatafile)?;
+
+        // Return uninitialized client
+        Ok(UninitializedClient::new(datafile))
     }
 
     /// Use a string variable as the datafile
+    ///
+    /// ```
+    /// use optimizely::Client;
+    ///
+    /// // Embed the datafile in the binary
+    /// const DATAFILE: &str = include_str!("../../../datafiles/sandbox.json");
+    /// let optimizely_client = Client::from_string(DATAFILE)?
+    ///     .initialize();
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
     pub fn from_string(content: &str) -> Result<UninitializedClient, ClientError> {
         // Create datafile from a string
         let datafile = Datafile::build(content).change_context(ClientError::InvalidDatafile)?;
@@ -79,37 +171,277 @@ impl Client {
         // Return uninitialized client
         Ok(UninitializedClient::new(datafile))
     }
+
+    /// Try several sources in order, the first source that succeeds is used
+    ///
+    /// If all sources fail, the error report contains the failure of every source.
+    /// If a fetcher failed before another source succeeded, the fetcher is kept,
+    /// so polling will replace the datafile once the CDN is reachable again.
+    ///
+    /// ```no_run
+    /// use optimizely::{client::DatafileSource, Client};
+    ///
+    /// // Use the bundled datafile when the datafile can not be downloaded
+    /// let optimizely_client = Client::from_sources([
+    ///     #[cfg(feature = "online")]
+    ///     DatafileSource::SdkKey("KVpGWnzPGKvvQ8yeEWmJZ".into()),
+    ///     DatafileSource::LocalFile("../datafiles/sandbox.json".into()),
+    /// ])?
+    /// .initialize();
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_sources<I>(sources: I) -> Result<UninitializedClient, ClientError>
+    where
+        I: IntoIterator<Item = DatafileSource>,
+    {
+        let mut failures: Option<Report<ClientError>> = None;
+        #[cfg(feature = "online")]
+        let mut failed_fetcher: Option<DatafileFetcher> = None;
+
+        for source in sources {
+            let description = source.to_string();
+            let result = match source {
+                #[cfg(feature = "online")]
+                DatafileSource::SdkKey(sdk_key) => {
+                    Client::from_fetcher_or_keep(DatafileFetcher::new(sdk_key), &mut failed_fetcher)
+                }
+                #[cfg(feature = "online")]
+                DatafileSource::Fetcher(fetcher) => Client::from_fetcher_or_keep(fetcher, &mut failed_fetcher),
+                #[cfg(feature = "online")]
+                DatafileSource::CacheFile(path) => Client::from_cache_file(path, DEFAULT_MAX_AGE),
+                #[cfg(feature = "online")]
+                DatafileSource::CacheFileWithMaxAge(path, max_age) => Client::from_cache_file(path, max_age),
+                DatafileSource::LocalFile(file_path) => Client::from_local_datafile(&file_path),
+                DatafileSource::String(content) => Client::from_string(&content),
+                DatafileSource::Embedded(content) => Client::from_string(content),
+                DatafileSource::Value(content) => Client::from_value(content),
+                #[cfg(feature = "snapshot")]
+                DatafileSource::Snapshot(snapshot) => Client::from_snapshot(&snapshot),
+            };
+
+            match result {
+                Ok(client) => {
+                    log::info!("Loaded datafile from {description}");
+
+                    // Refresh the datafile using the fetcher that failed
+                    #[cfg(feature = "online")]
+                    let client = client.or_with_fetcher(failed_fetcher);
+
+                    return Ok(client);
+                }
+                Err(report) => {
+                    log::warn!("Failed to load datafile from {description}");
+                    let report = report.attach_printable(format!("Datafile source: {description}"));
+                    match &mut failures {
+                        Some(failures) => failures.extend_one(report),
+                        None => failures = Some(report),
+                    }
+                }
+            }
+        }
+
+        match failures {
+            Some(failures) => Err(failures.change_context(ClientError::AllSourcesFailed)),
+            None => Err(report!(ClientError::DatafileMissing)),
+        }
+    }
+
+    /// Use a cache file as source, unless it is older than the maximum age
+    #[cfg(feature = "online")]
+    fn from_cache_file(path: PathBuf, max_age: Duration) -> Result<UninitializedClient, ClientError> {
+        let (datafile, _) = DatafileCache::from_path(path).load(max_age)?;
+        Ok(UninitializedClient::new(datafile))
+    }
+
+    /// Use a fetcher as source, but keep the first fetcher that failed
+    #[cfg(feature = "online")]
+    fn from_fetcher_or_keep(
+        mut fetcher: DatafileFetcher, failed_fetcher: &mut Option<DatafileFetcher>,
+    ) -> Result<UninitializedClient, ClientError> {
+        // Make GET request, but fall back to the cached datafile when it fails
+        match fetcher.fetch_or_load_cached() {
+            Ok(datafile) => {
+                let mut client = UninitializedClient::new(datafile);
+                client.set_fetcher(fetcher);
+                Ok(client)
+            }
+            Err(report) => {
+                failed_fetcher.get_or_insert(fetcher);
+                Err(report)
+            }
+        }
+    }
 }
 
 impl UninitializedClient {
     pub(super) fn new(datafile: Datafile) -> UninitializedClient {
         UninitializedClient {
             datafile,
-            _default_decide_options: None,
+            default_decide_options: DecideOptions::default(),
             _user_profile_service: None,
-            #[cfg(feature = "online")]
             event_dispatcher: None,
+            #[cfg(feature = "online")]
+            event_endpoint: None,
+            #[cfg(feature = "online")]
+            http_client: None,
+            #[cfg(feature = "online")]
+            fetcher: None,
+            #[cfg(feature = "online")]
+            polling_interval: None,
+            #[cfg(feature = "async")]
+            async_polling: false,
         }
     }
 
-    /// Use a custom event dispatcher
+    /// Keep the fetcher in case polling is enabled, and use the same HTTP client for the Event API
+    #[cfg(feature = "online")]
+    fn set_fetcher(&mut self, fetcher: DatafileFetcher) {
+        self.http_client = Some(fetcher.http_client());
+        self.fetcher = Some(fetcher);
+    }
+
+    /// Keep the given fetcher, unless the client already has a fetcher
     #[cfg(feature = "online")]
+    fn or_with_fetcher(mut self, fetcher: Option<DatafileFetcher>) -> UninitializedClient {
+        if let (Some(fetcher), None) = (fetcher, &self.fetcher) {
+            self.set_fetcher(fetcher);
+        }
+        self
+    }
+
+    /// Use a custom event dispatcher
+    ///
+    /// Without the `online` feature, no events are dispatched unless a custom event dispatcher is specified.
     pub fn with_event_dispatcher(mut self, event_dispatcher: impl EventDispatcher + 'static) -> UninitializedClient {
         self.event_dispatcher = Some(Box::new(event_dispatcher));
         self
     }
 
-    // TODO: implement with_default_decide_options and with_user_profile_service
+    /// Use a custom Event API endpoint instead of the default endpoint of the region in the datafile
+    #[cfg(feature = "online")]
+    pub fn with_event_endpoint<T: Into<String>>(mut self, event_endpoint: T) -> UninitializedClient {
+        self.event_endpoint = Some(event_endpoint.into());
+        self
+    }
+
+    /// Use a custom HTTP client to send events
+    #[cfg(feature = "online")]
+    pub fn with_http_client(mut self, http_client: impl HttpClient + 'static) -> UninitializedClient {
+        self.http_client = Some(Arc::new(http_client));
+        self
+    }
+
+    /// Periodically download the latest datafile in a background thread
+    ///
+    /// Polling is only possible when the client was created with an SDK key.
+    /// The thread stops when the client is dropped.
+    #[cfg(feature = "online")]
+    pub fn with_polling_interval(mut self, polling_interval: Duration) -> UninitializedClient {
+        self.polling_interval = Some(polling_interval);
+        self
+    }
+
+    /// Periodically download the latest datafile in a task on the current Tokio runtime
+    ///
+    /// Polling is only possible when the client was created with an SDK key.
+    /// The task is cancelled when the client is dropped.
+    /// When the client is not initialized within a Tokio runtime, a background thread is used instead.
+    #[cfg(feature = "async")]
+    pub fn with_async_polling_interval(mut self, polling_interval: Duration) -> UninitializedClient {
+        self.polling_interval = Some(polling_interval);
+        self.async_polling = true;
+        self
+    }
+
+    /// Use default options for every decision made by this client
+    ///
+    /// The default options are combined with the options of each call,
+    /// so an option is set when it is set in either of them.
+    ///
+    /// ```
+    /// use optimizely::{decision::DecideOptions, Client};
+    ///
+    /// // Never send decision events, for example in a staging environment
+    /// let default_decide_options = DecideOptions {
+    ///     disable_decision_event: true,
+    ///     ..DecideOptions::default()
+    /// };
+    ///
+    /// // Initialize Optimizely client using local datafile and default decide options
+    /// let file_path = "../datafiles/sandbox.json";
+    /// let optimizely_client = Client::from_local_datafile(file_path)?
+    ///     .with_default_decide_options(default_decide_options)
+    ///     .initialize();
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_default_decide_options(mut self, default_decide_options: DecideOptions) -> UninitializedClient {
+        self.default_decide_options = default_decide_options;
+        self
+    }
+
+    // TODO: implement with_user_profile_service
 
     /// Initialize the client
     pub fn initialize(self) -> Client {
+        // Store the datafile in a slot that can be shared with the poller
+        let datafile = Arc::new(RwLock::new(Arc::new(self.datafile)));
+
+        // Listeners are shared with the poller
+        let notification_center = Arc::new(NotificationCenter::default());
+
+        // Start polling if an interval was specified
+        #[cfg(feature = "online")]
+        let poller = match (self.fetcher, self.polling_interval) {
+            #[cfg(feature = "async")]
+            (Some(fetcher), Some(interval)) if self.async_polling => {
+                let slot = Arc::clone(&datafile);
+                let notification_center = Arc::clone(&notification_center);
+                match tokio::runtime::Handle::try_current() {
+                    Ok(runtime) => {
+                        Some(DatafilePoller::spawn_async(fetcher, interval, slot, notification_center, &runtime))
+                    }
+                    Err(_) => {
+                        log::warn!("Polling in a background thread, because there is no Tokio runtime");
+                        Some(DatafilePoller::spawn(fetcher, interval, slot, notification_center))
+                    }
+                }
+            }
+            (Some(fetcher), Some(interval)) => {
+                let slot = Arc::clone(&datafile);
+                let notification_center = Arc::clone(&notification_center);
+                Some(DatafilePoller::spawn(fetcher, interval, slot, notification_center))
+            }
+            (None, Some(_)) => {
+                log::warn!("Polling interval is ignored, because the client was not created with an SDK key");
+                None
+            }
+            _ => None,
+        };
+
+        // Without the online feature there is no built-in event dispatcher
+        #[cfg(feature = "online")]
+        let event_dispatcher = self
+            .event_dispatcher
+            .or_else(|| Some(Box::<SimpleEventDispatcher>::default()));
+        #[cfg(not(feature = "online"))]
+        let event_dispatcher = self.event_dispatcher;
+
         // Select default for any options that were not specified
         Client {
-            datafile: self.datafile,
+            datafile,
+            notification_center,
+            default_decide_options: self.default_decide_options,
+            #[cfg(feature = "online")]
+            _poller: poller,
+            event_dispatcher,
+            #[cfg(feature = "online")]
+            event_endpoint: self.event_endpoint,
             #[cfg(feature = "online")]
-            event_dispatcher: self
-                .event_dispatcher
-                .unwrap_or_else(|| Box::<SimpleEventDispatcher>::default()),
+            http_client: self
+                .http_client
+                .unwrap_or_else(|| Arc::new(UreqHttpClient::default())),
         }
     }
 }
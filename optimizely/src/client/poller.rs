@@ -0,0 +1,141 @@
+// External imports
+use error_stack::Result;
+use std::sync::{mpsc, Arc, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+// Imports from crate
+use crate::client::{ClientError, DatafileFetcher, DatafileSlot};
+use crate::datafile::Datafile;
+use crate::notification::{ConfigUpdateNotification, NotificationCenter};
+
+/// Background thread or task that periodically downloads the latest datafile
+///
+/// Every downloaded datafile with a new revision replaces the datafile in the slot.
+/// User contexts that were already created keep using the datafile they were created with.
+///
+/// The thread or task stops when the poller is dropped.
+pub(crate) struct DatafilePoller {
+    thread_handle: Option<thread::JoinHandle<()>>,
+    transmitter: Option<mpsc::Sender<()>>,
+    #[cfg(feature = "async")]
+    task_handle: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl DatafilePoller {
+    /// Start polling in a separate thread
+    pub(crate) fn spawn(
+        mut fetcher: DatafileFetcher, interval: Duration, slot: DatafileSlot,
+        notification_center: Arc<NotificationCenter>,
+    ) -> DatafilePoller {
+        // The channel is only used to signal the thread to stop
+        let (transmitter, receiver) = mpsc::channel::<()>();
+
+        let thread_handle = thread::spawn(move || {
+            // Wait for the interval, unless the transmitter is dropped in the meantime
+            while let Err(mpsc::RecvTimeoutError::Timeout) = receiver.recv_timeout(interval) {
+                log::debug!("Polling latest datafile");
+                DatafilePoller::update(&slot, &notification_center, fetcher.fetch_if_changed());
+            }
+        });
+
+        DatafilePoller {
+            thread_handle: Some(thread_handle),
+            transmitter: Some(transmitter),
+            #[cfg(feature = "async")]
+            task_handle: None,
+        }
+    }
+
+    /// Start polling in a task on the given Tokio runtime
+    ///
+    /// The HTTP client is blocking, so each request is made on a thread where blocking is acceptable.
+    #[cfg(feature = "async")]
+    pub(crate) fn spawn_async(
+        fetcher: DatafileFetcher, interval: Duration, slot: DatafileSlot, notification_center: Arc<NotificationCenter>,
+        runtime: &tokio::runtime::Handle,
+    ) -> DatafilePoller {
+        let task_handle = runtime.spawn(async move {
+            let mut fetcher_option = Some(fetcher);
+
+            while let Some(mut fetcher) = fetcher_option.take() {
+                tokio::time::sleep(interval).await;
+                log::debug!("Polling latest datafile");
+
+                // Move the fetcher to the blocking thread and back again
+                let blocking_result = tokio::task::spawn_blocking(move || {
+                    let result = fetcher.fetch_if_changed();
+                    (fetcher, result)
+                })
+                .await;
+
+                match blocking_result {
+                    Ok((fetcher, result)) => {
+                        DatafilePoller::update(&slot, &notification_center, result);
+                        fetcher_option = Some(fetcher);
+                    }
+                    Err(_) => {
+                        log::error!("Polling task was cancelled or panicked");
+                    }
+                }
+            }
+        });
+
+        DatafilePoller {
+            thread_handle: None,
+            transmitter: None,
+            task_handle: Some(task_handle),
+        }
+    }
+
+    fn update(
+        slot: &DatafileSlot, notification_center: &NotificationCenter, result: Result<Option<Datafile>, ClientError>,
+    ) {
+        let datafile = match result {
+            Ok(Some(datafile)) => datafile,
+            Ok(None) => return,
+            Err(report) => {
+                // Keep using the current datafile and retry after the next interval
+                log::error!("Failed to poll latest datafile");
+                log::error!("\n{report:?}");
+                return;
+            }
+        };
+
+        // Swap the datafile in the slot
+        let revision = datafile.revision();
+        let datafile = Arc::new(datafile);
+        let mut current = slot.write().unwrap_or_else(PoisonError::into_inner);
+        let previous = std::mem::replace(&mut *current, Arc::clone(&datafile));
+        drop(current);
+        log::info!("Updated datafile to revision {revision}");
+
+        // Notify listeners after releasing the lock, so listeners can use the client
+        notification_center.notify_config_update(|| ConfigUpdateNotification {
+            previous_revision: previous.revision(),
+            revision,
+            diff: previous.diff(&datafile),
+        });
+    }
+}
+
+impl Drop for DatafilePoller {
+    fn drop(&mut self) {
+        // Drop the transmitter first, so the thread will stop waiting
+        if let Some(tx) = self.transmitter.take() {
+            drop(tx);
+        }
+
+        // Wait until the thread has finished the current request
+        if let Some(handle) = self.thread_handle.take() {
+            // Ignore result
+            drop(handle.join());
+        }
+
+        // A task can not be awaited while dropping, so cancel it instead
+        #[cfg(feature = "async")]
+        if let Some(handle) = self.task_handle.take() {
+            handle.abort();
+        }
+    }
+}
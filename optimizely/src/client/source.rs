@@ -0,0 +1,63 @@
+// External imports
+use serde_json::Value;
+use std::fmt;
+#[cfg(feature = "online")]
+use std::path::PathBuf;
+#[cfg(feature = "online")]
+use std::time::Duration;
+
+// Imports from crate
+#[cfg(feature = "online")]
+use crate::client::DatafileFetcher;
+
+/// A source from which the datafile can be loaded when initializing the client
+///
+/// Used with [`Client::from_sources`](crate::Client::from_sources) to try several sources in order.
+pub enum DatafileSource {
+    /// Download the datafile from the CDN using an SDK key
+    ///
+    /// Use a fetcher instead to download the datafile with an access token or a custom URL template.
+    #[cfg(feature = "online")]
+    SdkKey(String),
+    /// Download the datafile using a configured fetcher
+    #[cfg(feature = "online")]
+    Fetcher(DatafileFetcher),
+    /// Read a cache file that was written by a fetcher with a cache directory, unless it is older than a week
+    #[cfg(feature = "online")]
+    CacheFile(PathBuf),
+    /// Read a cache file that was written by a fetcher with a cache directory, unless it is older than the given age
+    #[cfg(feature = "online")]
+    CacheFileWithMaxAge(PathBuf, Duration),
+    /// Read the datafile from the local filesystem
+    LocalFile(String),
+    /// Use a string as the datafile
+    String(String),
+    /// Use a static string as the datafile, for example one that is embedded with `include_str!`
+    Embedded(&'static str),
+    /// Use an already parsed JSON document as the datafile
+    Value(Value),
+    /// Load the datafile from a binary snapshot
+    #[cfg(feature = "snapshot")]
+    Snapshot(Vec<u8>),
+}
+
+impl fmt::Display for DatafileSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "online")]
+            DatafileSource::SdkKey(sdk_key) => write!(f, "SDK key {sdk_key}"),
+            #[cfg(feature = "online")]
+            DatafileSource::Fetcher(fetcher) => write!(f, "fetcher for {}", fetcher.url()),
+            #[cfg(feature = "online")]
+            DatafileSource::CacheFile(path) => write!(f, "cache file {}", path.display()),
+            #[cfg(feature = "online")]
+            DatafileSource::CacheFileWithMaxAge(path, _) => write!(f, "cache file {}", path.display()),
+            DatafileSource::LocalFile(file_path) => write!(f, "local datafile {file_path}"),
+            DatafileSource::String(_) => write!(f, "datafile string"),
+            DatafileSource::Embedded(_) => write!(f, "embedded datafile"),
+            DatafileSource::Value(_) => write!(f, "datafile JSON value"),
+            #[cfg(feature = "snapshot")]
+            DatafileSource::Snapshot(_) => write!(f, "datafile snapshot"),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+// External imports
+use thiserror::Error;
+
+/// This type represents all possible errors that can occur when tracking a conversion event
+#[derive(Error, Debug, PartialEq)]
+pub enum TrackError {
+    #[doc(hidden)]
+    #[error("Event key does not exist in datafile")]
+    UnknownEventKey,
+    #[doc(hidden)]
+    #[error("Event dispatcher queue is full")]
+    DispatcherQueueFull,
+    #[doc(hidden)]
+    #[error("Event dispatcher is closed")]
+    DispatcherClosed,
+    #[doc(hidden)]
+    #[error("Event dispatcher failed to send the event")]
+    DispatcherSendFailed,
+}
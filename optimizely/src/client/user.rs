@@ -1,24 +1,63 @@
 // External imports
+use error_stack::{report, Result, ResultExt};
 use fasthash::murmur3::hash32_with_seed as murmur3_hash;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 // Imports from crate
-use crate::conversion::Conversion;
-use crate::datafile::{Experiment, FeatureFlag, Variation};
+use crate::conversion::{Conversion, EventTags};
+use crate::datafile::{Datafile, Experiment, FeatureFlag, Variation};
 use crate::decision::{DecideOptions, Decision};
+use crate::event_api::DispatchError;
+use crate::notification::{DecideNotification, TrackNotification};
 
 // Imports from super
-use super::Client;
+use super::{Client, TrackError};
 
 /// Custom type alias for user attributes
 pub type UserAttributes = HashMap<String, String>;
 
+/// User context that does not borrow from the client or the user id
+///
+/// An owned user context can be stored, returned from functions or moved into other threads.
+///
+/// ```
+/// use optimizely::{client::OwnedUserContext, Client};
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// // Initialize Optimizely client using local datafile and share it
+/// let file_path = "../datafiles/sandbox.json";
+/// let optimizely_client = Arc::new(Client::from_local_datafile(file_path)?.initialize());
+///
+/// // Create a user context that owns its data
+/// let user_id = String::from("123abc789xyz");
+/// let user_context: OwnedUserContext = optimizely_client.create_owned_user_context(user_id);
+///
+/// // Decide a feature flag in another thread
+/// let handle = thread::spawn(move || user_context.decide("buy_button"));
+/// let decision = handle.join().unwrap();
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub type OwnedUserContext = UserContext<'static>;
+
+/// Reference to the client that is either borrowed or shared
+enum ClientHandle<'a> {
+    Borrowed(&'a Client),
+    Shared(Arc<Client>),
+}
+
 /// Constant used for the hashing algorithm
 const HASH_SEED: u32 = 1;
 
 /// Ranges are specified between 0 and 10_000
 const MAX_OF_RANGE: f64 = 10_000_f64;
 
+/// Bucketing keys up to this length are built on the stack instead of allocated
+const BUCKETING_KEY_BUFFER_SIZE: usize = 128;
+
 /// User specific context
 ///
 /// ```
@@ -48,17 +87,35 @@ const MAX_OF_RANGE: f64 = 10_000_f64;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub struct UserContext<'a> {
-    client: &'a Client,
-    user_id: &'a str,
+    client: ClientHandle<'a>,
+    datafile: Arc<Datafile>,
+    user_id: Cow<'a, str>,
     attributes: UserAttributes,
 }
 
 impl UserContext<'_> {
     // Only allow UserContext to be constructed from a Client
     pub(crate) fn new<'a>(client: &'a Client, user_id: &'a str, attributes: UserAttributes) -> UserContext<'a> {
+        // Use the same datafile for the lifetime of the user context
+        let datafile = client.datafile();
+
+        UserContext {
+            client: ClientHandle::Borrowed(client),
+            datafile,
+            user_id: Cow::Borrowed(user_id),
+            attributes,
+        }
+    }
+
+    // Only allow OwnedUserContext to be constructed from a shared Client
+    pub(crate) fn new_owned(client: Arc<Client>, user_id: String, attributes: UserAttributes) -> OwnedUserContext {
+        // Use the same datafile for the lifetime of the user context
+        let datafile = client.datafile();
+
         UserContext {
-            client,
-            user_id,
+            client: ClientHandle::Shared(client),
+            datafile,
+            user_id: Cow::Owned(user_id),
             attributes,
         }
     }
@@ -75,12 +132,20 @@ impl UserContext<'_> {
 
     /// Get the client instance
     pub fn client(&self) -> &Client {
-        self.client
+        match &self.client {
+            ClientHandle::Borrowed(client) => client,
+            ClientHandle::Shared(client) => client,
+        }
+    }
+
+    /// Get the datafile that is used for decisions of this user context
+    pub fn datafile(&self) -> &Datafile {
+        &self.datafile
     }
 
     /// Get the id of a user
     pub fn user_id(&self) -> &str {
-        self.user_id
+        &self.user_id
     }
 
     /// Get all attributes of a user
@@ -89,43 +154,102 @@ impl UserContext<'_> {
         &self.attributes
     }
 
-    #[cfg(feature = "online")]
     /// Track a conversion event (without properties and tags) for this user
+    ///
+    /// Errors are logged, use [`UserContext::try_track_event`] to handle them instead.
     pub fn track_event(&self, event_key: &str) {
-        let properties = HashMap::default();
-        let tags = HashMap::default();
-        self.track_event_with_properties_and_tags(event_key, properties, tags)
+        log_track_error(self.try_track_event(event_key))
     }
 
-    #[cfg(feature = "online")]
     /// Track a conversion event with properties (but without tags) for this user
     pub fn track_event_with_properties(&self, event_key: &str, properties: HashMap<String, String>) {
-        let tags = HashMap::default();
-        self.track_event_with_properties_and_tags(event_key, properties, tags)
+        log_track_error(self.try_track_event_with_properties(event_key, properties))
     }
 
-    #[cfg(feature = "online")]
     /// Track a conversion event with properties and tags for this user
+    ///
+    /// The `revenue` tag (integer in cents) and the `value` tag (number) are also sent as top-level fields of the event.
     pub fn track_event_with_properties_and_tags(
-        &self, event_key: &str, properties: HashMap<String, String>, tags: HashMap<String, String>,
+        &self, event_key: &str, properties: HashMap<String, String>, tags: EventTags,
     ) {
+        log_track_error(self.try_track_event_with_properties_and_tags(event_key, properties, tags))
+    }
+
+    /// Track a conversion event (without properties and tags) for this user, returning an error on failure
+    ///
+    /// ```
+    /// use optimizely::{client::TrackError, Client};
+    ///
+    /// // Initialize Optimizely client using local datafile
+    /// let file_path = "../datafiles/sandbox.json";
+    /// let optimizely_client = Client::from_local_datafile(file_path)?
+    ///     .initialize();
+    ///
+    /// // Tracking an event key that is not in the datafile fails
+    /// let user_context = optimizely_client.create_user_context("123abc789xyz");
+    /// let report = user_context.try_track_event("purchsae").unwrap_err();
+    /// assert_eq!(report.current_context(), &TrackError::UnknownEventKey);
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_track_event(&self, event_key: &str) -> Result<(), TrackError> {
+        let properties = HashMap::default();
+        let tags = EventTags::default();
+        self.try_track_event_with_properties_and_tags(event_key, properties, tags)
+    }
+
+    /// Track a conversion event with properties (but without tags) for this user, returning an error on failure
+    pub fn try_track_event_with_properties(
+        &self, event_key: &str, properties: HashMap<String, String>,
+    ) -> Result<(), TrackError> {
+        let tags = EventTags::default();
+        self.try_track_event_with_properties_and_tags(event_key, properties, tags)
+    }
+
+    /// Track a conversion event with properties and tags for this user, returning an error on failure
+    pub fn try_track_event_with_properties_and_tags(
+        &self, event_key: &str, properties: HashMap<String, String>, tags: EventTags,
+    ) -> Result<(), TrackError> {
         // Find the event key in the datafile
-        match self.client.datafile().event(event_key) {
-            Some(event) => {
-                log::debug!("Logging conversion event");
+        let Some(event) = self.datafile.event(event_key) else {
+            return Err(report!(TrackError::UnknownEventKey))
+                .attach_printable(format!("Event key \"{event_key}\" does not exist in datafile"));
+        };
 
-                // Create conversion to send to dispatcher
-                let conversion = Conversion::new(event_key, event.id(), properties, tags);
+        log::debug!("Logging conversion event");
 
-                // Ignore result of the send_decision function
-                self.client
-                    .event_dispatcher()
-                    .send_conversion_event(self, conversion);
-            }
-            None => {
-                log::warn!("Event key does not exist in datafile");
-            }
-        }
+        // Create conversion to send to dispatcher
+        let conversion = Conversion::new(event_key, event.id(), properties, tags);
+
+        // Notify listeners before the conversion is moved to the dispatcher
+        let notification = TrackNotification {
+            event_key,
+            user_id: self.user_id(),
+            attributes: self.attributes(),
+            properties: conversion.properties(),
+            tags: conversion.tags(),
+        };
+        self.client()
+            .notification_center()
+            .notify_track(&notification);
+
+        // Without an event dispatcher the conversion is only passed to the listeners
+        let Some(event_dispatcher) = self.client().optional_event_dispatcher() else {
+            log::debug!("No event dispatcher to send conversion event");
+            return Ok(());
+        };
+
+        // Hand the conversion to the dispatcher
+        event_dispatcher
+            .send_conversion_event(self, conversion)
+            .map_err(|report| {
+                let context = match report.current_context() {
+                    DispatchError::QueueFull => TrackError::DispatcherQueueFull,
+                    DispatchError::Closed => TrackError::DispatcherClosed,
+                    DispatchError::SendFailed => TrackError::DispatcherSendFailed,
+                };
+                report.change_context(context)
+            })
     }
 
     /// Decide which variation to show to a user
@@ -135,14 +259,60 @@ impl UserContext<'_> {
     }
 
     /// Decide which variation to show to a user
+    ///
+    /// The given options are combined with the default decide options of the client.
     pub fn decide_with_options(&self, flag_key: &str, options: &DecideOptions) -> Decision {
+        // Combine with the default options of the client
+        let options = &self.client().default_decide_options().merge(options);
+
+        // Only collect reasons when requested
+        let mut reasons = options.include_reasons.then(Vec::new);
+
+        // Get the decision and whether to send a decision event
+        let (decision, send_decision) = self.decide_flag(flag_key, options, &mut reasons);
+        let decision = decision.with_reasons(reasons.unwrap_or_default());
+
+        // Only a decision event that was accepted by the dispatcher counts as dispatched
+        let decision_event_dispatched = match self.client().optional_event_dispatcher() {
+            Some(event_dispatcher) if send_decision => {
+                let result = event_dispatcher.send_decision_event(self, decision.clone());
+                if let Err(report) = &result {
+                    log::error!("Failed to dispatch decision event");
+                    log::error!("\n{report:?}");
+                }
+                result.is_ok()
+            }
+            _ => false,
+        };
+
+        // Notify listeners about the decision
+        let notification = DecideNotification {
+            flag_key,
+            user_id: self.user_id(),
+            attributes: self.attributes(),
+            decision: &decision,
+            decision_event_dispatched,
+        };
+        self.client()
+            .notification_center()
+            .notify_decide(&notification);
+
+        // Return
+        decision
+    }
+
+    fn decide_flag(
+        &self, flag_key: &str, options: &DecideOptions, reasons: &mut Option<Vec<String>>,
+    ) -> (Decision, bool) {
         // Retrieve Flag object
-        let flag = match self.client.datafile().flag(flag_key) {
+        let flag = match self.datafile.flag(flag_key) {
             Some(flag) => flag,
             None => {
                 // When flag key cannot be found, return the off variation
                 // CONSIDERATION: Could have used Result<Decision, E> but this is how other Optimizely SDKs work
-                return Decision::off(flag_key);
+                // An unknown flag key is not in the datafile, so it is the only string that is copied
+                add_reason(reasons, || format!("No flag was found for key \"{flag_key}\"."));
+                return (Decision::off(Arc::from(flag_key)), false);
             }
         };
 
@@ -152,35 +322,43 @@ impl UserContext<'_> {
         // Get the selected variation for the given flag
         let decision = match self.decide_variation_for_flag(flag, &mut send_decision) {
             Some((experiment, variation)) => {
-                // Unpack the variation and create Decision struct
-                Decision::new(
-                        flag_key,
-                        experiment.campaign_id(),
-                        experiment.id(),
-                        variation.id(),
+                add_reason(reasons, || {
+                    format!(
+                        "User \"{}\" is in variation \"{}\" of experiment \"{}\".",
+                        self.user_id(),
                         variation.key(),
-                        variation.is_feature_enabled(),
+                        experiment.key()
                     )
+                });
+
+                // Unpack the variation and create Decision struct, sharing the strings of the datafile
+                Decision::new(
+                    Arc::clone(flag.shared_key()),
+                    Arc::clone(experiment.shared_campaign_id()),
+                    Arc::clone(experiment.shared_id()),
+                    Arc::clone(variation.shared_id()),
+                    Arc::clone(variation.shared_key()),
+                    variation.is_feature_enabled(),
+                )
             }
             None => {
                 // No experiment or rollout found, or user does not qualify for any
-                Decision::off(flag_key)
+                add_reason(reasons, || {
+                    format!("User \"{}\" is not in any experiment or rollout of flag \"{flag_key}\".", self.user_id())
+                });
+                Decision::off(Arc::clone(flag.shared_key()))
             }
         };
 
-        #[cfg(feature = "online")]
-        if send_decision {
-            self.client.event_dispatcher().send_decision_event(&self, decision.clone());
-        }
-
-        // Return
-        decision
+        (decision, send_decision)
     }
 
-    fn decide_variation_for_flag(&self, flag: &FeatureFlag, send_decision: &mut bool) -> Option<(&Experiment, &Variation)> {
+    fn decide_variation_for_flag(
+        &self, flag: &FeatureFlag, send_decision: &mut bool,
+    ) -> Option<(&Experiment, &Variation)> {
         // Find first Experiment for which this user qualifies
         let result = flag.experiments_ids().iter().find_map(|experiment_id| {
-            let experiment = self.client.datafile().experiment(experiment_id);
+            let experiment = self.datafile.experiment(experiment_id);
 
             match experiment {
                 Some(experiment) => self.decide_variation_for_experiment(experiment),
@@ -200,7 +378,7 @@ impl UserContext<'_> {
                 *send_decision = false;
 
                 // No direct experiment found, let's look at the Rollout
-                let rollout = self.client.datafile().rollout(flag.rollout_id()).unwrap(); // TODO: remove unwrap
+                let rollout = self.datafile.rollout(flag.rollout_id()).unwrap(); // TODO: remove unwrap
 
                 // Find the first experiment within the Rollout for which this user qualifies
                 rollout
@@ -212,31 +390,56 @@ impl UserContext<'_> {
     }
 
     fn decide_variation_for_experiment<'a>(
-        &'a self, experiment: &'a Experiment
+        &'a self, experiment: &'a Experiment,
     ) -> Option<(&'a Experiment, &'a Variation)> {
         // Use references for the ids
         let user_id = self.user_id();
         let experiment_id = experiment.id();
 
-        // Concatenate user id and experiment id
-        let bucketing_key = format!("{user_id}{experiment_id}");
+        // Concatenate user id and experiment id as bytes, on the stack if it fits
+        let length = user_id.len() + experiment_id.len();
+        let mut buffer = [0_u8; BUCKETING_KEY_BUFFER_SIZE];
+        let mut heap_buffer = Vec::new();
+        let bucketing_key = if length <= BUCKETING_KEY_BUFFER_SIZE {
+            buffer[..user_id.len()].copy_from_slice(user_id.as_bytes());
+            buffer[user_id.len()..length].copy_from_slice(experiment_id.as_bytes());
+            &buffer[..length]
+        } else {
+            heap_buffer.reserve_exact(length);
+            heap_buffer.extend_from_slice(user_id.as_bytes());
+            heap_buffer.extend_from_slice(experiment_id.as_bytes());
+            &heap_buffer[..]
+        };
 
-        // To hash the bucket key it needs to be converted to an array of `u8` bytes
         // Use Murmur3 (32-bit) with seed
-        let hash_value = murmur3_hash(bucketing_key.as_bytes(), HASH_SEED);
+        let hash_value = murmur3_hash(bucketing_key, HASH_SEED);
 
         // Bring the hash into a range of 0 to 10_000
         let bucket_value = ((hash_value as f64) / (u32::MAX as f64) * MAX_OF_RANGE) as u64;
 
         // Get the variation ID according to the traffic allocation
-        experiment.traffic_allocation()
+        experiment
+            .traffic_allocation()
             .variation(bucket_value)
             // Map it to a Variation struct
-            .map(|variation_id| experiment.variation(variation_id))
-            .flatten()
+            .and_then(|variation_id| experiment.variation(variation_id))
             // Combine it with the experiment
-            .map(|variation| Some((experiment, variation)))
-            .flatten()
+            .map(|variation| (experiment, variation))
+    }
+}
+
+/// Add a reason to the decision, only formatting the message if reasons are collected
+fn add_reason(reasons: &mut Option<Vec<String>>, reason: impl FnOnce() -> String) {
+    if let Some(reasons) = reasons {
+        reasons.push(reason());
+    }
+}
+
+/// Log the error of tracking a conversion event
+fn log_track_error(result: Result<(), TrackError>) {
+    if let Err(report) = result {
+        log::warn!("Failed to track conversion event");
+        log::warn!("\n{report:?}");
     }
 }
 
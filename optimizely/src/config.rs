@@ -0,0 +1,147 @@
+//! Read-only view of the datafile
+//!
+//! The types in the datafile module are internal, because their layout follows the JSON document.
+//! The [`OptimizelyConfig`] is a public snapshot that lists what the client knows about,
+//! for example to show the flags of a service in an admin dashboard.
+
+// External imports
+use serde::Serialize;
+
+// Imports from crate
+use crate::datafile::Datafile;
+
+// Relative imports of sub modules
+pub use attribute::OptimizelyAttribute;
+pub use audience::OptimizelyAudience;
+pub use event::OptimizelyEvent;
+pub use experiment::OptimizelyExperiment;
+pub use flag::OptimizelyFlag;
+pub use variable::OptimizelyVariable;
+pub use variation::OptimizelyVariation;
+
+mod attribute;
+mod audience;
+mod event;
+mod experiment;
+mod flag;
+mod variable;
+mod variation;
+
+/// Read-only view of the flags, experiments, audiences, events and attributes in a datafile
+///
+/// Flags, events, attributes and variations are sorted by key, audiences are sorted by ID since they have no key.
+/// The rules of a flag are in order of evaluation and its variables are in the order of the datafile.
+///
+/// ```
+/// use optimizely::Client;
+///
+/// // Initialize Optimizely client using local datafile
+/// let file_path = "../datafiles/sandbox.json";
+/// let optimizely_client = Client::from_local_datafile(file_path)?
+///     .initialize();
+///
+/// // List all flags with their rules
+/// let config = optimizely_client.optimizely_config();
+/// for flag in config.flags() {
+///     let experiment_count = flag.experiment_rules().len();
+///     let delivery_count = flag.delivery_rules().len();
+///     println!("{}: {experiment_count} experiments, {delivery_count} deliveries", flag.key());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyConfig {
+    account_id: String,
+    project_id: String,
+    environment_key: String,
+    revision: u32,
+    flags: Vec<OptimizelyFlag>,
+    audiences: Vec<OptimizelyAudience>,
+    events: Vec<OptimizelyEvent>,
+    attributes: Vec<OptimizelyAttribute>,
+}
+
+impl OptimizelyConfig {
+    pub(crate) fn new(datafile: &Datafile) -> OptimizelyConfig {
+        let mut flags = datafile
+            .flags()
+            .map(|flag| OptimizelyFlag::new(datafile, flag))
+            .collect::<Vec<_>>();
+        flags.sort_by(|a, b| a.key().cmp(b.key()));
+
+        let mut audiences = datafile
+            .audiences()
+            .map(OptimizelyAudience::new)
+            .collect::<Vec<_>>();
+        audiences.sort_by(|a, b| a.id().cmp(b.id()));
+
+        let mut events = datafile
+            .events()
+            .map(OptimizelyEvent::new)
+            .collect::<Vec<_>>();
+        events.sort_by(|a, b| a.key().cmp(b.key()));
+
+        let mut attributes = datafile
+            .attributes()
+            .map(OptimizelyAttribute::new)
+            .collect::<Vec<_>>();
+        attributes.sort_by(|a, b| a.key().cmp(b.key()));
+
+        OptimizelyConfig {
+            account_id: datafile.account_id().into(),
+            project_id: datafile.project_id().into(),
+            environment_key: datafile.environment_key().into(),
+            revision: datafile.revision(),
+            flags,
+            audiences,
+            events,
+            attributes,
+        }
+    }
+
+    /// Get the account ID
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    /// Get the project ID
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    /// Get the key of the environment
+    pub fn environment_key(&self) -> &str {
+        &self.environment_key
+    }
+
+    /// Get the revision of the datafile
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    /// Get all flags
+    pub fn flags(&self) -> &[OptimizelyFlag] {
+        &self.flags
+    }
+
+    /// Get the flag with the given key
+    pub fn flag(&self, flag_key: &str) -> Option<&OptimizelyFlag> {
+        self.flags.iter().find(|flag| flag.key() == flag_key)
+    }
+
+    /// Get all audiences
+    pub fn audiences(&self) -> &[OptimizelyAudience] {
+        &self.audiences
+    }
+
+    /// Get all events
+    pub fn events(&self) -> &[OptimizelyEvent] {
+        &self.events
+    }
+
+    /// Get all attributes
+    pub fn attributes(&self) -> &[OptimizelyAttribute] {
+        &self.attributes
+    }
+}
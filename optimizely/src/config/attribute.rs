@@ -0,0 +1,31 @@
+// External imports
+use serde::Serialize;
+
+// Imports from crate
+use crate::datafile::Attribute;
+
+/// Attribute that can be used in audience conditions
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyAttribute {
+    id: String,
+    key: String,
+}
+
+impl OptimizelyAttribute {
+    pub(crate) fn new(attribute: &Attribute) -> OptimizelyAttribute {
+        OptimizelyAttribute {
+            id: attribute.id().into(),
+            key: attribute.key().into(),
+        }
+    }
+
+    /// Get the attribute ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the attribute key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
@@ -0,0 +1,38 @@
+// External imports
+use serde::Serialize;
+
+// Imports from crate
+use crate::datafile::Audience;
+
+/// Audience with its conditions serialized as a JSON string
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyAudience {
+    id: String,
+    name: String,
+    conditions: String,
+}
+
+impl OptimizelyAudience {
+    pub(crate) fn new(audience: &Audience) -> OptimizelyAudience {
+        OptimizelyAudience {
+            id: audience.id().into(),
+            name: audience.name().into(),
+            conditions: audience.conditions().into(),
+        }
+    }
+
+    /// Get the audience ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the name of the audience
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the conditions of the audience as a JSON string
+    pub fn conditions(&self) -> &str {
+        &self.conditions
+    }
+}
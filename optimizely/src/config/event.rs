@@ -0,0 +1,38 @@
+// External imports
+use serde::Serialize;
+
+// Imports from crate
+use crate::datafile::Event;
+
+/// Event that can be tracked as a conversion
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyEvent {
+    id: String,
+    key: String,
+    experiment_ids: Vec<String>,
+}
+
+impl OptimizelyEvent {
+    pub(crate) fn new(event: &Event) -> OptimizelyEvent {
+        OptimizelyEvent {
+            id: event.id().into(),
+            key: event.key().into(),
+            experiment_ids: event.experiment_ids().to_vec(),
+        }
+    }
+
+    /// Get the event ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the event key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the IDs of the experiments that use this event as a metric
+    pub fn experiment_ids(&self) -> &[String] {
+        &self.experiment_ids
+    }
+}
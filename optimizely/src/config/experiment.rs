@@ -0,0 +1,80 @@
+// External imports
+use serde::Serialize;
+use serde_json::Value;
+use std::iter;
+
+// Imports from crate
+use crate::datafile::{Experiment, FeatureFlag};
+
+// Imports from super
+use super::OptimizelyVariation;
+
+/// Experiment rule or delivery rule of a flag
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyExperiment {
+    id: String,
+    key: String,
+    audience_ids: Vec<String>,
+    audience_conditions: String,
+    variations: Vec<OptimizelyVariation>,
+}
+
+impl OptimizelyExperiment {
+    pub(crate) fn new(experiment: &Experiment, flag: &FeatureFlag) -> OptimizelyExperiment {
+        let mut variations = experiment
+            .variations()
+            .map(|variation| OptimizelyVariation::new(variation, flag))
+            .collect::<Vec<_>>();
+        variations.sort_by(|a, b| a.key().cmp(b.key()));
+
+        // Without audience conditions, the audience IDs are combined with "or"
+        let audience_conditions = match (experiment.audience_conditions(), experiment.audience_ids()) {
+            (Some(conditions), _) => conditions.to_string(),
+            (None, []) => String::new(),
+            (None, audience_ids) => {
+                let conditions = iter::once("or").chain(audience_ids.iter().map(String::as_str));
+                Value::from_iter(conditions).to_string()
+            }
+        };
+
+        OptimizelyExperiment {
+            id: experiment.id().into(),
+            key: experiment.key().into(),
+            audience_ids: experiment.audience_ids().to_vec(),
+            audience_conditions,
+            variations,
+        }
+    }
+
+    /// Get the experiment ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the experiment key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the IDs of the audiences that are targeted
+    pub fn audience_ids(&self) -> &[String] {
+        &self.audience_ids
+    }
+
+    /// Get the audience conditions as a JSON string, empty when no audiences are targeted
+    pub fn audience_conditions(&self) -> &str {
+        &self.audience_conditions
+    }
+
+    /// Get all variations
+    pub fn variations(&self) -> &[OptimizelyVariation] {
+        &self.variations
+    }
+
+    /// Get the variation with the given key
+    pub fn variation(&self, variation_key: &str) -> Option<&OptimizelyVariation> {
+        self.variations
+            .iter()
+            .find(|variation| variation.key() == variation_key)
+    }
+}
@@ -0,0 +1,81 @@
+// External imports
+use serde::Serialize;
+
+// Imports from crate
+use crate::datafile::{Datafile, FeatureFlag};
+
+// Imports from super
+use super::{OptimizelyExperiment, OptimizelyVariable};
+
+/// Feature flag with its rules and variables
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyFlag {
+    id: String,
+    key: String,
+    experiment_rules: Vec<OptimizelyExperiment>,
+    delivery_rules: Vec<OptimizelyExperiment>,
+    variables: Vec<OptimizelyVariable>,
+}
+
+impl OptimizelyFlag {
+    pub(crate) fn new(datafile: &Datafile, flag: &FeatureFlag) -> OptimizelyFlag {
+        // Experiments in the order in which they are evaluated
+        let experiment_rules = flag
+            .experiments_ids()
+            .iter()
+            .filter_map(|experiment_id| datafile.experiment(experiment_id))
+            .map(|experiment| OptimizelyExperiment::new(experiment, flag))
+            .collect();
+
+        // Deliveries in the order in which they are evaluated, the last one is "Everyone Else"
+        let delivery_rules = datafile
+            .rollout(flag.rollout_id())
+            .map(|rollout| {
+                rollout
+                    .experiments()
+                    .iter()
+                    .map(|experiment| OptimizelyExperiment::new(experiment, flag))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let variables = flag
+            .variables()
+            .iter()
+            .map(|variable| OptimizelyVariable::new(variable, variable.default_value()))
+            .collect();
+
+        OptimizelyFlag {
+            id: flag.id().into(),
+            key: flag.key().into(),
+            experiment_rules,
+            delivery_rules,
+            variables,
+        }
+    }
+
+    /// Get the flag ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the flag key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the experiment rules, in order of evaluation
+    pub fn experiment_rules(&self) -> &[OptimizelyExperiment] {
+        &self.experiment_rules
+    }
+
+    /// Get the delivery rules, in order of evaluation
+    pub fn delivery_rules(&self) -> &[OptimizelyExperiment] {
+        &self.delivery_rules
+    }
+
+    /// Get the variables with their default values
+    pub fn variables(&self) -> &[OptimizelyVariable] {
+        &self.variables
+    }
+}
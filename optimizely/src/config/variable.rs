@@ -0,0 +1,46 @@
+// External imports
+use serde::Serialize;
+
+// Imports from crate
+use crate::datafile::Variable;
+
+/// Variable of a flag with its value as a string
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyVariable {
+    id: String,
+    key: String,
+    #[serde(rename = "type")]
+    variable_type: String,
+    value: String,
+}
+
+impl OptimizelyVariable {
+    pub(crate) fn new(variable: &Variable, value: &str) -> OptimizelyVariable {
+        OptimizelyVariable {
+            id: variable.id().into(),
+            key: variable.key().into(),
+            variable_type: variable.variable_type().into(),
+            value: value.into(),
+        }
+    }
+
+    /// Get the variable ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the variable key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the type of the variable, like `string`, `boolean`, `integer`, `double` or `json`
+    pub fn variable_type(&self) -> &str {
+        &self.variable_type
+    }
+
+    /// Get the value of the variable as a string
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
@@ -0,0 +1,61 @@
+// External imports
+use serde::Serialize;
+
+// Imports from crate
+use crate::datafile::{FeatureFlag, Variation};
+
+// Imports from super
+use super::OptimizelyVariable;
+
+/// Variation of an experiment or delivery with the values of the flag variables
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizelyVariation {
+    id: String,
+    key: String,
+    feature_enabled: bool,
+    variables: Vec<OptimizelyVariable>,
+}
+
+impl OptimizelyVariation {
+    pub(crate) fn new(variation: &Variation, flag: &FeatureFlag) -> OptimizelyVariation {
+        // Variables are only overridden when the flag is enabled in this variation
+        let variables = flag
+            .variables()
+            .iter()
+            .map(|variable| {
+                let value = variation
+                    .variable_value(variable.id())
+                    .filter(|_| variation.is_feature_enabled())
+                    .unwrap_or(variable.default_value());
+                OptimizelyVariable::new(variable, value)
+            })
+            .collect();
+
+        OptimizelyVariation {
+            id: variation.id().into(),
+            key: variation.key().into(),
+            feature_enabled: variation.is_feature_enabled(),
+            variables,
+        }
+    }
+
+    /// Get the variation ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the variation key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get whether the flag is enabled in this variation
+    pub fn feature_enabled(&self) -> bool {
+        self.feature_enabled
+    }
+
+    /// Get the variables with their values in this variation
+    pub fn variables(&self) -> &[OptimizelyVariable] {
+        &self.variables
+    }
+}
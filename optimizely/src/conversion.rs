@@ -1,19 +1,27 @@
 //! A conversion event
 
+use serde_json::Value;
 use std::collections::HashMap;
 
+/// Custom type alias for event tags, tag values can be strings, numbers or booleans
+pub type EventTags = HashMap<String, Value>;
+
+// Reserved tag keys that are also sent as top-level fields of the event
+const REVENUE_TAG_KEY: &str = "revenue";
+const VALUE_TAG_KEY: &str = "value";
+
 /// A conversion event
 #[derive(Debug)]
 pub struct Conversion {
     event_key: String,
     event_id: String,
     properties: HashMap<String, String>,
-    tags: HashMap<String, String>,
+    tags: EventTags,
 }
 
 impl Conversion {
     pub(crate) fn new<T: Into<String>>(
-        event_key: T, event_id: T, properties: HashMap<String, String>, tags: HashMap<String, String>,
+        event_key: T, event_id: T, properties: HashMap<String, String>, tags: EventTags,
     ) -> Conversion {
         Conversion {
             event_key: event_key.into(),
@@ -41,7 +49,55 @@ impl Conversion {
     }
 
     /// Get tags
-    pub fn tags(&self) -> &HashMap<String, String> {
+    pub fn tags(&self) -> &EventTags {
         &self.tags
     }
+
+    /// Get the revenue in cents from the `revenue` tag
+    ///
+    /// The tag should be an integer, or a string containing an integer.
+    pub fn revenue(&self) -> Option<i64> {
+        let revenue = match self.tags.get(REVENUE_TAG_KEY)? {
+            Value::Number(number) => number.as_i64(),
+            Value::String(string) => string.parse().ok(),
+            _ => None,
+        };
+
+        if revenue.is_none() {
+            log::warn!("Ignoring revenue tag, because it is not an integer");
+        }
+        revenue
+    }
+
+    /// Get the numeric value from the `value` tag
+    ///
+    /// The tag should be a number, or a string containing a number.
+    pub fn value(&self) -> Option<f64> {
+        let value = match self.tags.get(VALUE_TAG_KEY)? {
+            Value::Number(number) => number.as_f64(),
+            Value::String(string) => string.parse().ok(),
+            _ => None,
+        };
+
+        if value.is_none() {
+            log::warn!("Ignoring value tag, because it is not a number");
+        }
+        value
+    }
+}
+
+/// Macro to create EventTags
+#[macro_export]
+macro_rules! event_tags {
+    { $( $key: expr => $value: expr),* $(,)?} => {
+        {
+            let mut tags = optimizely::conversion::EventTags::new();
+
+            $(
+                tags.insert($key.into(), $value.into());
+            )*
+
+            tags
+        }
+    };
 }
@@ -1,27 +1,62 @@
 //! Parsing the Optimizely datafile
 
 // External imports
-use error_stack::{Result, ResultExt};
+use error_stack::{report, Report, Result};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+
+// Imports from crate
+use crate::config::OptimizelyConfig;
 
 // Relative imports of sub modules
+pub(crate) use attribute::Attribute;
+pub(crate) use audience::Audience;
+#[cfg(feature = "test-util")]
+pub use builder::{DatafileBuilder, ExperimentBuilder, FlagBuilder, VariationBuilder};
+pub use diff::DatafileDiff;
 use environment::Environment;
+#[cfg(feature = "online")]
+use environment::Revision;
+use environment::Version;
 pub use error::DatafileError;
 pub(crate) use event::Event;
 pub(crate) use experiment::Experiment;
 pub(crate) use feature_flag::FeatureFlag;
+use group::Group;
+pub use region::Region;
 use rollout::Rollout;
+#[cfg(feature = "snapshot")]
+pub use snapshot::SNAPSHOT_FORMAT_VERSION;
 use traffic_allocation::TrafficAllocation;
+pub(crate) use variable::Variable;
 pub(crate) use variation::Variation;
 
+mod attribute;
+mod audience;
+#[cfg(feature = "test-util")]
+mod builder;
+mod diff;
 mod environment;
 mod error;
 mod event;
 mod experiment;
 mod feature_flag;
+mod group;
+mod region;
 mod rollout;
+#[cfg(feature = "snapshot")]
+mod snapshot;
 mod traffic_allocation;
+mod variable;
 mod variation;
 
+/// Versions of the datafile format that are supported by this SDK
+///
+/// Unknown fields are ignored, so new fields can be added to these versions without breaking the SDK.
+pub const SUPPORTED_VERSIONS: [u32; 3] = [2, 3, 4];
+
 /// The datafile contains all the feature flags, experiments, events and other configuration from an Optimizely account.
 ///
 /// This configuration is stored in JSON format.
@@ -32,18 +67,136 @@ mod variation;
 /// containing the `content`.
 /// This would mean that a lot of memory would stay allocated for JSON syntax and unused properties.
 /// Instead the relevant fields are copied into their own `String`s.
-#[derive(Debug)]
+///
+/// A `Datafile` can be serialized back into JSON, which includes all the fields that are used by the SDK.
+/// Typed audiences are written as regular audiences, with their conditions as a string.
+///
+/// ```
+/// use optimizely::datafile::Datafile;
+///
+/// let content = std::fs::read_to_string("../datafiles/sandbox.json")?;
+/// let datafile = Datafile::build(&content)?;
+///
+/// // Write the datafile back to JSON and build it again
+/// let json = serde_json::to_string(&datafile)?;
+/// let copy = Datafile::build(&json)?;
+/// assert_eq!(copy.revision(), datafile.revision());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
 pub struct Datafile(Environment);
 
 impl Datafile {
     /// Construct a new Datafile from a string containing a JSON document
+    ///
+    /// Datafiles that are embedded in the binary with `include_str!` can be used directly.
     pub fn build(content: &str) -> Result<Datafile, DatafileError> {
+        Datafile::from_slice(content.as_bytes())
+    }
+
+    /// Construct a new Datafile from bytes containing a JSON document, like a downloaded object
+    pub fn from_slice(content: &[u8]) -> Result<Datafile, DatafileError> {
         // Parse the JSON content via Serde into Rust structs
-        let environment: Environment = serde_json::from_str(content).change_context(DatafileError::InvalidJson)?;
+        let environment: Environment = Datafile::parse_slice(content).map_err(|report| {
+            // Only parse the version when the structure is not as expected, since that might be the cause
+            Datafile::unsupported_version_or(Datafile::parse_slice(content), report)
+        })?;
+
+        Datafile::from_environment(environment)
+    }
+
+    /// Construct a new Datafile from a reader containing a JSON document, like a file
+    ///
+    /// The document is parsed while it is read, without keeping a copy of the content in memory.
+    /// Unbuffered readers, like a `File`, should be wrapped in a `BufReader`.
+    ///
+    /// The content can not be read twice, so the version is only checked when the structure is as expected.
+    /// Use [`Datafile::from_slice`] to report an unsupported version of a datafile with a different structure.
+    pub fn from_reader(reader: impl Read) -> Result<Datafile, DatafileError> {
+        // Parse the JSON content via Serde into Rust structs while reading
+        let environment: Environment = Datafile::parse(&mut serde_json::Deserializer::from_reader(reader))?;
+
+        Datafile::from_environment(environment)
+    }
+
+    /// Construct a new Datafile from an already parsed JSON document
+    ///
+    /// The strings in the document are moved into the datafile instead of copied.
+    pub fn from_value(content: Value) -> Result<Datafile, DatafileError> {
+        // Check the version first, since other versions might have a different structure
+        // The document is already parsed, so this only looks up a single field
+        let version: Version = Datafile::parse(&content)?;
+        Datafile::check_version(version.version())?;
+
+        // Convert the JSON document via Serde into Rust structs
+        let environment: Environment = Datafile::parse(content)?;
+
+        Datafile::from_environment(environment)
+    }
+
+    /// Parse only the revision from a string containing a JSON document
+    ///
+    /// This is used to avoid building a new Datafile when the revision has not changed.
+    #[cfg(feature = "online")]
+    pub(crate) fn parse_revision(content: &str) -> Result<u32, DatafileError> {
+        let revision: Revision = Datafile::parse_slice(content.as_bytes())?;
+
+        Ok(revision.revision())
+    }
+
+    fn check_version(version: u32) -> Result<(), DatafileError> {
+        if !SUPPORTED_VERSIONS.contains(&version) {
+            return Err(report!(DatafileError::UnsupportedVersion { version }));
+        }
+
+        Ok(())
+    }
+
+    /// Report an unsupported version instead of the given error, since other versions might have a different structure
+    fn unsupported_version_or(
+        version: Result<Version, DatafileError>, report: Report<DatafileError>,
+    ) -> Report<DatafileError> {
+        version
+            .ok()
+            .and_then(|version| Datafile::check_version(version.version()).err())
+            .unwrap_or(report)
+    }
+
+    fn from_environment(mut environment: Environment) -> Result<Datafile, DatafileError> {
+        // Only supported versions of the datafile format can be used
+        Datafile::check_version(environment.version())?;
+
+        // Build the indices once, so lookups by key are as fast as lookups by ID
+        environment.build_indices();
+
+        // All references between entities should be resolvable
+        environment.validate()?;
 
         Ok(Datafile(environment))
     }
 
+    /// Parse bytes containing a JSON document
+    fn parse_slice<T: DeserializeOwned>(content: &[u8]) -> Result<T, DatafileError> {
+        Datafile::parse(&mut serde_json::Deserializer::from_slice(content))
+    }
+
+    /// Deserialize a JSON document, keeping track of the path for detailed errors
+    fn parse<'de, D, T>(deserializer: D) -> Result<T, DatafileError>
+    where
+        D: Deserializer<'de, Error = serde_json::Error>,
+        T: Deserialize<'de>,
+    {
+        serde_path_to_error::deserialize(deserializer).map_err(|error| {
+            Report::new(DatafileError::from_path_error(&error)).attach_printable(error.inner().to_string())
+        })
+    }
+
+    /// Get the version of the datafile format
+    pub fn version(&self) -> u32 {
+        self.0.version()
+    }
+
     /// Get the account ID
     pub fn account_id(&self) -> &str {
         self.0.account_id()
@@ -54,14 +207,24 @@ impl Datafile {
         self.0.revision()
     }
 
+    /// Get the data residency region of the account
+    pub fn region(&self) -> Region {
+        self.0.region()
+    }
+
     /// Get the flag with the given key
     pub fn flag(&self, flag_key: &str) -> Option<&FeatureFlag> {
         self.0.feature_flags().get(flag_key)
     }
 
-    /// Get the experiment with the given experiment ID
+    /// Get the experiment or rollout rule with the given experiment ID
     pub fn experiment(&self, experiment_id: &str) -> Option<&Experiment> {
-        self.0.experiments().get(experiment_id)
+        self.0.experiment(experiment_id)
+    }
+
+    /// Get the experiment or rollout rule with the given experiment key
+    pub fn experiment_by_key(&self, experiment_key: &str) -> Option<&Experiment> {
+        self.0.experiment_by_key(experiment_key)
     }
 
     /// Get the rollout with the given rollout ID
@@ -73,4 +236,86 @@ impl Datafile {
     pub fn event(&self, event_key: &str) -> Option<&Event> {
         self.0.events().get(event_key)
     }
+
+    /// Get the ID of the attribute with the given key
+    pub fn attribute_id(&self, attribute_key: &str) -> Option<&str> {
+        self.0.attributes().get(attribute_key).map(Attribute::id)
+    }
+
+    /// Get the audience with the given audience ID
+    pub fn audience(&self, audience_id: &str) -> Option<&Audience> {
+        self.0.audiences().get(audience_id)
+    }
+
+    /// Get the ID of the project
+    pub fn project_id(&self) -> &str {
+        self.0.project_id()
+    }
+
+    /// Get the key of the environment, like `production` or `development`
+    pub fn environment_key(&self) -> &str {
+        self.0.environment_key()
+    }
+
+    /// Get a read-only view of the flags, experiments, audiences, events and attributes
+    pub fn optimizely_config(&self) -> OptimizelyConfig {
+        OptimizelyConfig::new(self)
+    }
+
+    /// Get the changes between this datafile and a newer datafile
+    ///
+    /// ```
+    /// use optimizely::datafile::Datafile;
+    ///
+    /// let content = std::fs::read_to_string("../datafiles/sandbox.json")?;
+    /// let previous = Datafile::build(&content)?;
+    /// let datafile = Datafile::build(&content)?;
+    ///
+    /// // Nothing changed between these datafiles
+    /// let diff = previous.diff(&datafile);
+    /// assert!(diff.is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn diff(&self, other: &Datafile) -> DatafileDiff {
+        DatafileDiff::new(self, other)
+    }
+
+    pub(crate) fn flags(&self) -> impl Iterator<Item = &FeatureFlag> {
+        self.0.feature_flags().values()
+    }
+
+    pub(crate) fn experiments(&self) -> impl Iterator<Item = &Experiment> {
+        self.0.all_experiments()
+    }
+
+    pub(crate) fn events(&self) -> impl Iterator<Item = &Event> {
+        self.0.events().values()
+    }
+
+    pub(crate) fn attributes(&self) -> impl Iterator<Item = &Attribute> {
+        self.0.attributes().values()
+    }
+
+    pub(crate) fn audiences(&self) -> impl Iterator<Item = &Audience> {
+        self.0.audiences().values()
+    }
+}
+
+/// Serialize a map of entities as an array, like the original datafile
+///
+/// The entities are ordered by their key in the map, so the output does not depend on the order of the `HashMap`.
+fn serialize_values<K, T, S>(map: &HashMap<K, T>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    K: Ord,
+    T: Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(sorted_values(map))
+}
+
+/// Get the values of a map ordered by their key, so iterating does not depend on the order of the `HashMap`
+fn sorted_values<K: Ord, T>(map: &HashMap<K, T>) -> Vec<&T> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    entries.into_iter().map(|(_, value)| value).collect()
 }
@@ -0,0 +1,57 @@
+// External imports
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// An audience with its conditions
+///
+/// Legacy audiences store the conditions as a string containing JSON,
+/// while typed audiences store the conditions as JSON.
+/// In both cases the conditions are kept as a serialized string,
+/// which is also how they are written when serializing the audience.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Audience {
+    id: String,
+    name: String,
+    #[serde(deserialize_with = "deserialize_conditions")]
+    conditions: String,
+}
+
+fn deserialize_conditions<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(conditions) => Ok(conditions),
+        conditions => Ok(conditions.to_string()),
+    }
+}
+
+impl Audience {
+    // Method to deserialize an array of Audiences into a Hashmap of Audiences
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Audience>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = HashMap::new();
+        for audience in Vec::<Audience>::deserialize(deserializer)? {
+            map.insert(audience.id.clone(), audience);
+        }
+        Ok(map)
+    }
+
+    /// Getter for `id` field
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Getter for `name` field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Getter for `conditions` field
+    pub fn conditions(&self) -> &str {
+        &self.conditions
+    }
+}
@@ -0,0 +1,184 @@
+//! Building a datafile in code, instead of loading a JSON file
+
+// External imports
+use error_stack::Result;
+use serde_json::{json, Value};
+
+// Imports from super
+use super::{Datafile, DatafileError, Region};
+
+// Relative imports of sub modules
+pub use experiment::ExperimentBuilder;
+pub use flag::FlagBuilder;
+pub use variation::VariationBuilder;
+
+mod experiment;
+mod flag;
+mod variation;
+
+/// Builder for a datafile with exactly the flags, events, attributes and audiences that a test needs
+///
+/// All other fields of the datafile get a sensible default value.
+/// Every flag gets its own rollout, which contains the rollout rules of the flag.
+///
+/// ```
+/// use optimizely::datafile::{DatafileBuilder, ExperimentBuilder, FlagBuilder, VariationBuilder};
+///
+/// // A/B test that puts every user in the "on" variation
+/// let experiment = ExperimentBuilder::new("banner_experiment", "200")
+///     .with_variation(VariationBuilder::new("on", "2000"))
+///     .with_traffic_allocation("2000", 10_000);
+///
+/// let datafile = DatafileBuilder::new()
+///     .with_revision(42)
+///     .with_event("purchase", "300")
+///     .with_flag(FlagBuilder::new("banner", "100").with_experiment(experiment))
+///     .build()?;
+///
+/// assert_eq!(datafile.revision(), 42);
+/// assert!(datafile.flag("banner").is_some());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DatafileBuilder {
+    account_id: String,
+    project_id: String,
+    environment_key: String,
+    revision: u32,
+    region: Region,
+    events: Vec<Value>,
+    attributes: Vec<Value>,
+    audiences: Vec<Value>,
+    flags: Vec<FlagBuilder>,
+}
+
+impl Default for DatafileBuilder {
+    fn default() -> Self {
+        DatafileBuilder::new()
+    }
+}
+
+impl DatafileBuilder {
+    /// Start with a datafile without any flags, events, attributes or audiences
+    pub fn new() -> DatafileBuilder {
+        DatafileBuilder {
+            account_id: String::from("10000"),
+            project_id: String::from("10000"),
+            environment_key: String::from("production"),
+            revision: 1,
+            region: Region::default(),
+            events: Vec::new(),
+            attributes: Vec::new(),
+            audiences: Vec::new(),
+            flags: Vec::new(),
+        }
+    }
+
+    /// Use a specific account ID
+    pub fn with_account_id<T: Into<String>>(mut self, account_id: T) -> DatafileBuilder {
+        self.account_id = account_id.into();
+        self
+    }
+
+    /// Use a specific project ID
+    pub fn with_project_id<T: Into<String>>(mut self, project_id: T) -> DatafileBuilder {
+        self.project_id = project_id.into();
+        self
+    }
+
+    /// Use a specific environment key
+    pub fn with_environment_key<T: Into<String>>(mut self, environment_key: T) -> DatafileBuilder {
+        self.environment_key = environment_key.into();
+        self
+    }
+
+    /// Use a specific revision
+    pub fn with_revision(mut self, revision: u32) -> DatafileBuilder {
+        self.revision = revision;
+        self
+    }
+
+    /// Use a specific data residency region
+    pub fn with_region(mut self, region: Region) -> DatafileBuilder {
+        self.region = region;
+        self
+    }
+
+    /// Add an event that can be tracked
+    pub fn with_event<T: Into<String>>(mut self, event_key: T, event_id: T) -> DatafileBuilder {
+        self.events.push(json!({
+            "id": event_id.into(),
+            "key": event_key.into(),
+            "experimentIds": [],
+        }));
+        self
+    }
+
+    /// Add an attribute that can be used in audience conditions
+    pub fn with_attribute<T: Into<String>>(mut self, attribute_key: T, attribute_id: T) -> DatafileBuilder {
+        self.attributes.push(json!({
+            "id": attribute_id.into(),
+            "key": attribute_key.into(),
+        }));
+        self
+    }
+
+    /// Add a typed audience with its conditions as JSON
+    pub fn with_audience<T: Into<String>>(mut self, audience_id: T, name: T, conditions: Value) -> DatafileBuilder {
+        self.audiences.push(json!({
+            "id": audience_id.into(),
+            "name": name.into(),
+            "conditions": conditions,
+        }));
+        self
+    }
+
+    /// Add a flag, including its experiments and rollout rules
+    pub fn with_flag(mut self, flag: FlagBuilder) -> DatafileBuilder {
+        self.flags.push(flag);
+        self
+    }
+
+    /// Create the JSON document of the datafile
+    pub fn to_value(&self) -> Value {
+        let experiments = self
+            .flags
+            .iter()
+            .flat_map(FlagBuilder::experiments)
+            .collect::<Vec<_>>();
+        let rollouts = self
+            .flags
+            .iter()
+            .map(FlagBuilder::rollout)
+            .collect::<Vec<_>>();
+        let feature_flags = self.flags.iter().map(FlagBuilder::flag).collect::<Vec<_>>();
+
+        json!({
+            "version": "4",
+            "accountId": self.account_id,
+            "projectId": self.project_id,
+            "environmentKey": self.environment_key,
+            "revision": self.revision.to_string(),
+            "botFiltering": false,
+            "anonymizeIP": true,
+            "region": self.region,
+            "events": self.events,
+            "attributes": self.attributes,
+            "audiences": [],
+            "typedAudiences": self.audiences,
+            "experiments": experiments,
+            "rollouts": rollouts,
+            "featureFlags": feature_flags,
+        })
+    }
+
+    /// Create the JSON document of the datafile as a string
+    pub fn to_json(&self) -> String {
+        self.to_value().to_string()
+    }
+
+    /// Build the datafile, which is validated just like a downloaded datafile
+    pub fn build(&self) -> Result<Datafile, DatafileError> {
+        Datafile::from_value(self.to_value())
+    }
+}
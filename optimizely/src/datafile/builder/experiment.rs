@@ -0,0 +1,74 @@
+// External imports
+use serde_json::{json, Value};
+
+// Imports from super
+use super::VariationBuilder;
+
+/// Builder for an experiment or a rollout rule with its variations and traffic allocation
+#[derive(Debug, Clone)]
+pub struct ExperimentBuilder {
+    id: String,
+    key: String,
+    audience_ids: Vec<String>,
+    variations: Vec<VariationBuilder>,
+    traffic_allocation: Vec<Value>,
+}
+
+impl ExperimentBuilder {
+    /// Start with an experiment without any variations and without any traffic
+    pub fn new<T: Into<String>>(experiment_key: T, experiment_id: T) -> ExperimentBuilder {
+        ExperimentBuilder {
+            id: experiment_id.into(),
+            key: experiment_key.into(),
+            audience_ids: Vec::new(),
+            variations: Vec::new(),
+            traffic_allocation: Vec::new(),
+        }
+    }
+
+    /// Only include users that are in the audience with the given ID
+    pub fn with_audience_id<T: Into<String>>(mut self, audience_id: T) -> ExperimentBuilder {
+        self.audience_ids.push(audience_id.into());
+        self
+    }
+
+    /// Add a variation
+    pub fn with_variation(mut self, variation: VariationBuilder) -> ExperimentBuilder {
+        self.variations.push(variation);
+        self
+    }
+
+    /// Allocate traffic to a variation, up to the given end of range
+    ///
+    /// Ranges should be added in ascending order and the end of the last range is at most 10,000.
+    /// An empty variation ID is used for traffic that is not allocated to any variation.
+    pub fn with_traffic_allocation<T: Into<String>>(mut self, variation_id: T, end_of_range: u64) -> ExperimentBuilder {
+        self.traffic_allocation.push(json!({
+            "entityId": variation_id.into(),
+            "endOfRange": end_of_range,
+        }));
+        self
+    }
+
+    pub(super) fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub(super) fn experiment(&self) -> Value {
+        let variations = self
+            .variations
+            .iter()
+            .map(VariationBuilder::variation)
+            .collect::<Vec<_>>();
+
+        json!({
+            "id": self.id,
+            "key": self.key,
+            "status": "Running",
+            "layerId": format!("layer-{}", self.id),
+            "audienceIds": self.audience_ids,
+            "trafficAllocation": self.traffic_allocation,
+            "variations": variations,
+        })
+    }
+}
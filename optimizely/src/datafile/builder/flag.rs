@@ -0,0 +1,100 @@
+// External imports
+use serde_json::{json, Value};
+
+// Imports from super
+use super::ExperimentBuilder;
+
+/// Builder for a flag with its variables, experiments and rollout rules
+#[derive(Debug, Clone)]
+pub struct FlagBuilder {
+    id: String,
+    key: String,
+    variables: Vec<Value>,
+    experiments: Vec<ExperimentBuilder>,
+    rollout_rules: Vec<ExperimentBuilder>,
+}
+
+impl FlagBuilder {
+    /// Start with a flag without any variables, experiments or rollout rules
+    pub fn new<T: Into<String>>(flag_key: T, flag_id: T) -> FlagBuilder {
+        FlagBuilder {
+            id: flag_id.into(),
+            key: flag_key.into(),
+            variables: Vec::new(),
+            experiments: Vec::new(),
+            rollout_rules: Vec::new(),
+        }
+    }
+
+    /// Add a variable, where the type is one of `string`, `integer`, `double`, `boolean` or `json`
+    pub fn with_variable<T: Into<String>>(
+        mut self, variable_key: T, variable_id: T, variable_type: T, default_value: T,
+    ) -> FlagBuilder {
+        let mut variable = json!({
+            "id": variable_id.into(),
+            "key": variable_key.into(),
+            "type": variable_type.into(),
+            "defaultValue": default_value.into(),
+        });
+
+        // JSON variables are stored as a string with sub type `json`
+        if variable["type"] == "json" {
+            variable["type"] = Value::from("string");
+            variable["subType"] = Value::from("json");
+        }
+        self.variables.push(variable);
+        self
+    }
+
+    /// Add an experiment, which is evaluated before the rollout rules
+    pub fn with_experiment(mut self, experiment: ExperimentBuilder) -> FlagBuilder {
+        self.experiments.push(experiment);
+        self
+    }
+
+    /// Add a rule to the rollout of this flag, rules are evaluated in the order they are added
+    pub fn with_rollout_rule(mut self, rule: ExperimentBuilder) -> FlagBuilder {
+        self.rollout_rules.push(rule);
+        self
+    }
+
+    fn rollout_id(&self) -> String {
+        format!("rollout-{}", self.id)
+    }
+
+    pub(super) fn flag(&self) -> Value {
+        let experiment_ids = self
+            .experiments
+            .iter()
+            .map(ExperimentBuilder::id)
+            .collect::<Vec<_>>();
+
+        json!({
+            "id": self.id,
+            "key": self.key,
+            "rolloutId": self.rollout_id(),
+            "experimentIds": experiment_ids,
+            "variables": self.variables,
+        })
+    }
+
+    pub(super) fn experiments(&self) -> Vec<Value> {
+        self.experiments
+            .iter()
+            .map(ExperimentBuilder::experiment)
+            .collect()
+    }
+
+    pub(super) fn rollout(&self) -> Value {
+        let rules = self
+            .rollout_rules
+            .iter()
+            .map(ExperimentBuilder::experiment)
+            .collect::<Vec<_>>();
+
+        json!({
+            "id": self.rollout_id(),
+            "experiments": rules,
+        })
+    }
+}
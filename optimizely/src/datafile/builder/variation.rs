@@ -0,0 +1,47 @@
+// External imports
+use serde_json::{json, Value};
+
+/// Builder for a variation with its variable values
+#[derive(Debug, Clone)]
+pub struct VariationBuilder {
+    id: String,
+    key: String,
+    is_feature_enabled: bool,
+    variables: Vec<Value>,
+}
+
+impl VariationBuilder {
+    /// Start with a variation that enables the feature, without any variable values
+    pub fn new<T: Into<String>>(variation_key: T, variation_id: T) -> VariationBuilder {
+        VariationBuilder {
+            id: variation_id.into(),
+            key: variation_key.into(),
+            is_feature_enabled: true,
+            variables: Vec::new(),
+        }
+    }
+
+    /// Enable or disable the feature, like the "off" variation does
+    pub fn with_feature_enabled(mut self, is_feature_enabled: bool) -> VariationBuilder {
+        self.is_feature_enabled = is_feature_enabled;
+        self
+    }
+
+    /// Override the default value of the variable with the given ID
+    pub fn with_variable_value<T: Into<String>>(mut self, variable_id: T, value: T) -> VariationBuilder {
+        self.variables.push(json!({
+            "id": variable_id.into(),
+            "value": value.into(),
+        }));
+        self
+    }
+
+    pub(super) fn variation(&self) -> Value {
+        json!({
+            "id": self.id,
+            "key": self.key,
+            "featureEnabled": self.is_feature_enabled,
+            "variables": self.variables,
+        })
+    }
+}
@@ -0,0 +1,180 @@
+// External imports
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+// Imports from super
+use super::Datafile;
+
+/// Changes between two revisions of a datafile
+///
+/// Flags and events are identified by key, audiences by ID.
+/// All lists are sorted, so the same changes always result in the same diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DatafileDiff {
+    previous_revision: u32,
+    revision: u32,
+    flags_added: Vec<String>,
+    flags_removed: Vec<String>,
+    traffic_allocations_changed: Vec<String>,
+    variations_added: Vec<(String, String)>,
+    audiences_added: Vec<String>,
+    audiences_removed: Vec<String>,
+    audiences_modified: Vec<String>,
+    events_added: Vec<String>,
+    events_removed: Vec<String>,
+}
+
+impl DatafileDiff {
+    pub(crate) fn new(previous: &Datafile, datafile: &Datafile) -> DatafileDiff {
+        let mut diff = DatafileDiff {
+            previous_revision: previous.revision(),
+            revision: datafile.revision(),
+            ..DatafileDiff::default()
+        };
+
+        // Flags
+        let previous_flags = previous
+            .flags()
+            .map(|flag| flag.key())
+            .collect::<BTreeSet<_>>();
+        let flags = datafile
+            .flags()
+            .map(|flag| flag.key())
+            .collect::<BTreeSet<_>>();
+        diff.flags_added = difference(&flags, &previous_flags);
+        diff.flags_removed = difference(&previous_flags, &flags);
+
+        // Experiments and rollout rules that exist in both datafiles
+        for experiment in datafile.experiments() {
+            let Some(previous_experiment) = previous.experiment_by_key(experiment.key()) else {
+                continue;
+            };
+
+            if experiment.traffic_allocation() != previous_experiment.traffic_allocation() {
+                diff.traffic_allocations_changed
+                    .push(experiment.key().into());
+            }
+
+            for variation in experiment.variations() {
+                if previous_experiment.variation(variation.id()).is_none() {
+                    diff.variations_added
+                        .push((experiment.key().into(), variation.key().into()));
+                }
+            }
+        }
+        diff.traffic_allocations_changed.sort_unstable();
+        diff.variations_added.sort_unstable();
+
+        // Audiences
+        let previous_audiences = previous
+            .audiences()
+            .map(|audience| audience.id())
+            .collect::<BTreeSet<_>>();
+        let audiences = datafile
+            .audiences()
+            .map(|audience| audience.id())
+            .collect::<BTreeSet<_>>();
+        diff.audiences_added = difference(&audiences, &previous_audiences);
+        diff.audiences_removed = difference(&previous_audiences, &audiences);
+        diff.audiences_modified = audiences
+            .intersection(&previous_audiences)
+            .filter(|audience_id| match (previous.audience(audience_id), datafile.audience(audience_id)) {
+                (Some(previous_audience), Some(audience)) => {
+                    previous_audience.name() != audience.name()
+                        || previous_audience.conditions() != audience.conditions()
+                }
+                _ => false,
+            })
+            .map(|audience_id| String::from(*audience_id))
+            .collect();
+
+        // Events
+        let previous_events = previous
+            .events()
+            .map(|event| event.key())
+            .collect::<BTreeSet<_>>();
+        let events = datafile
+            .events()
+            .map(|event| event.key())
+            .collect::<BTreeSet<_>>();
+        diff.events_added = difference(&events, &previous_events);
+        diff.events_removed = difference(&previous_events, &events);
+
+        diff
+    }
+
+    /// Whether nothing changed, apart from the revision
+    pub fn is_empty(&self) -> bool {
+        self.flags_added.is_empty()
+            && self.flags_removed.is_empty()
+            && self.traffic_allocations_changed.is_empty()
+            && self.variations_added.is_empty()
+            && self.audiences_added.is_empty()
+            && self.audiences_removed.is_empty()
+            && self.audiences_modified.is_empty()
+            && self.events_added.is_empty()
+            && self.events_removed.is_empty()
+    }
+
+    /// Getter for `previous_revision` field
+    pub fn previous_revision(&self) -> u32 {
+        self.previous_revision
+    }
+
+    /// Getter for `revision` field
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    /// Keys of the flags that were added
+    pub fn flags_added(&self) -> &[String] {
+        &self.flags_added
+    }
+
+    /// Keys of the flags that were removed
+    pub fn flags_removed(&self) -> &[String] {
+        &self.flags_removed
+    }
+
+    /// Keys of the experiments and rollout rules of which the traffic allocation changed
+    pub fn traffic_allocations_changed(&self) -> &[String] {
+        &self.traffic_allocations_changed
+    }
+
+    /// Keys of the experiments or rollout rules and the keys of the variations that were added to them
+    pub fn variations_added(&self) -> &[(String, String)] {
+        &self.variations_added
+    }
+
+    /// IDs of the audiences that were added
+    pub fn audiences_added(&self) -> &[String] {
+        &self.audiences_added
+    }
+
+    /// IDs of the audiences that were removed
+    pub fn audiences_removed(&self) -> &[String] {
+        &self.audiences_removed
+    }
+
+    /// IDs of the audiences of which the name or conditions changed
+    pub fn audiences_modified(&self) -> &[String] {
+        &self.audiences_modified
+    }
+
+    /// Keys of the events that were added
+    pub fn events_added(&self) -> &[String] {
+        &self.events_added
+    }
+
+    /// Keys of the events that were removed
+    pub fn events_removed(&self) -> &[String] {
+        &self.events_removed
+    }
+}
+
+// Items that are in the first set, but not in the second set, in sorted order
+fn difference(set: &BTreeSet<&str>, other: &BTreeSet<&str>) -> Vec<String> {
+    set.difference(other)
+        .map(|item| String::from(*item))
+        .collect()
+}
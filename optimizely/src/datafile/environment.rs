@@ -1,35 +1,92 @@
 // External imports
-use serde::{Deserialize, Deserializer};
-use std::collections::HashMap;
+use error_stack::{report, Report};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 // Imports from super
-use super::{Event, Experiment, FeatureFlag, Rollout};
+#[cfg(feature = "snapshot")]
+use super::snapshot::{self, Snapshot};
+use super::{
+    serialize_values, Attribute, Audience, DatafileError, Event, Experiment, FeatureFlag, Group, Region, Rollout,
+};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Environment {
+    #[serde(deserialize_with = "deserialize_numeric_string", serialize_with = "serialize_numeric_string")]
+    version: u32,
     #[serde(rename = "accountId")]
     account_id: String,
     #[serde(rename = "projectId")]
     project_id: String,
     #[serde(rename = "environmentKey")]
     environment_key: String,
-    #[serde(deserialize_with = "deserialize_revision")]
+    #[serde(deserialize_with = "deserialize_numeric_string", serialize_with = "serialize_numeric_string")]
     revision: u32,
     #[serde(rename = "botFiltering")]
     bot_filtering: bool,
     #[serde(rename = "anonymizeIP")]
     anonymize_ip: bool,
-    #[serde(rename = "events", deserialize_with = "Event::deserialize")]
-    events: HashMap<String, Event>,
-    #[serde(deserialize_with = "Experiment::deserialize")]
-    experiments: HashMap<String, Experiment>,
-    #[serde(deserialize_with = "Rollout::deserialize")]
+    #[serde(default, deserialize_with = "Region::deserialize_or_default")]
+    region: Region,
+    #[serde(rename = "events", deserialize_with = "Event::deserialize", serialize_with = "serialize_values")]
+    events: HashMap<Arc<str>, Event>,
+    #[serde(deserialize_with = "Experiment::deserialize", serialize_with = "serialize_values")]
+    experiments: HashMap<Arc<str>, Experiment>,
+    #[serde(deserialize_with = "Rollout::deserialize", serialize_with = "serialize_values")]
     rollouts: HashMap<String, Rollout>,
-    #[serde(rename = "featureFlags", deserialize_with = "FeatureFlag::deserialize")]
-    feature_flags: HashMap<String, FeatureFlag>,
+    #[serde(default, deserialize_with = "Group::deserialize", serialize_with = "serialize_values")]
+    groups: HashMap<String, Group>,
+    #[serde(
+        rename = "featureFlags",
+        deserialize_with = "FeatureFlag::deserialize",
+        serialize_with = "serialize_values"
+    )]
+    feature_flags: HashMap<Arc<str>, FeatureFlag>,
+    #[serde(default, deserialize_with = "Audience::deserialize", serialize_with = "serialize_values")]
+    audiences: HashMap<String, Audience>,
+    // Typed audiences are merged into the audiences when building the datafile
+    #[serde(rename = "typedAudiences", default, deserialize_with = "Audience::deserialize", skip_serializing)]
+    typed_audiences: HashMap<String, Audience>,
+    #[serde(default, deserialize_with = "Attribute::deserialize", serialize_with = "serialize_values")]
+    attributes: HashMap<String, Attribute>,
+    #[serde(skip)]
+    experiment_ids_by_key: HashMap<Arc<str>, Arc<str>>,
+    #[serde(skip)]
+    rollout_rule_positions: HashMap<Arc<str>, (String, usize)>,
+}
+
+/// Only the version of the datafile format, which is checked when the whole environment can not be parsed
+#[derive(Deserialize, Debug)]
+pub struct Version {
+    #[serde(deserialize_with = "deserialize_numeric_string")]
+    version: u32,
+}
+
+impl Version {
+    /// Getter for `version` field
+    pub fn version(&self) -> u32 {
+        self.version
+    }
 }
 
-fn deserialize_revision<'de, D>(deserializer: D) -> Result<u32, D::Error>
+/// Only the revision of the environment, which is cheaper to parse than the whole environment
+#[cfg(feature = "online")]
+#[derive(Deserialize, Debug)]
+pub struct Revision {
+    #[serde(deserialize_with = "deserialize_numeric_string")]
+    revision: u32,
+}
+
+#[cfg(feature = "online")]
+impl Revision {
+    /// Getter for `revision` field
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+}
+
+fn deserialize_numeric_string<'de, D>(deserializer: D) -> Result<u32, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -38,18 +95,172 @@ where
         .map_err(serde::de::Error::custom)
 }
 
+fn serialize_numeric_string<S>(value: &u32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
 impl Environment {
+    /// Build the indices for lookups by key, which is done once when building the datafile
+    pub fn build_indices(&mut self) {
+        // Typed audiences take precedence over legacy audiences with the same ID
+        self.audiences.extend(self.typed_audiences.drain());
+
+        // Experiments of A/B tests, of which the keys are unique after validating
+        for experiment in self.experiments.values_mut() {
+            experiment.build_index();
+            self.experiment_ids_by_key
+                .insert(Arc::clone(experiment.shared_key()), Arc::clone(experiment.shared_id()));
+        }
+
+        // Rules of rollouts are not in the list of experiments, so remember their position
+        for rollout in self.rollouts.values_mut() {
+            let rollout_id = rollout.id().to_owned();
+            for (position, rule) in rollout.experiments_mut().iter_mut().enumerate() {
+                rule.build_index();
+                self.experiment_ids_by_key
+                    .insert(Arc::clone(rule.shared_key()), Arc::clone(rule.shared_id()));
+                self.rollout_rule_positions
+                    .insert(Arc::clone(rule.shared_id()), (rollout_id.clone(), position));
+            }
+        }
+    }
+
+    /// Check that all references between entities can be resolved and that keys are unique
+    ///
+    /// Entities are checked in the order of the datafile, so the same problem is reported every time.
+    /// Like errors while parsing, the path identifies list items by their index in the datafile.
+    pub fn validate(&self) -> Result<(), Report<DatafileError>> {
+        for flag in in_datafile_order(&self.feature_flags, FeatureFlag::position) {
+            // Flags without a rollout have an empty rollout ID
+            let rollout_id = flag.rollout_id();
+            if !rollout_id.is_empty() && !self.rollouts.contains_key(rollout_id) {
+                let path = format!("featureFlags[{}].rolloutId", flag.position());
+                return Err(dangling_reference(path, rollout_id));
+            }
+
+            if let Some((index, experiment_id)) = flag
+                .experiments_ids()
+                .iter()
+                .enumerate()
+                .find(|(_, experiment_id)| self.experiment(experiment_id).is_none() && !self.is_grouped(experiment_id))
+            {
+                let path = format!("featureFlags[{}].experimentIds[{index}]", flag.position());
+                return Err(dangling_reference(path, experiment_id));
+            }
+        }
+
+        for experiment in in_datafile_order(&self.experiments, Experiment::position) {
+            let path = format!("experiments[{}].trafficAllocation", experiment.position());
+            validate_traffic_allocation(experiment, path)?;
+        }
+
+        for rollout in in_datafile_order(&self.rollouts, Rollout::position) {
+            for (index, rule) in rollout.experiments().iter().enumerate() {
+                let path = format!("rollouts[{}].experiments[{index}].trafficAllocation", rollout.position());
+                validate_traffic_allocation(rule, path)?;
+            }
+        }
+
+        for group in in_datafile_order(&self.groups, Group::position) {
+            for (index, experiment) in group.experiments().iter().enumerate() {
+                let path = format!("groups[{}].experiments[{index}].trafficAllocation", group.position());
+                validate_traffic_allocation(experiment, path)?;
+            }
+
+            // The traffic of a group is allocated to its own experiments
+            if let Some(experiment_id) = group
+                .traffic_allocation()
+                .variation_ids()
+                .find(|experiment_id| {
+                    !experiment_id.is_empty()
+                        && !group
+                            .experiments()
+                            .iter()
+                            .any(|experiment| experiment.id() == *experiment_id)
+                })
+            {
+                let path = format!("groups[{}].trafficAllocation", group.position());
+                return Err(dangling_reference(path, experiment_id));
+            }
+        }
+
+        // Experiments and rules of rollouts are looked up by key in the same index
+        let mut keys = HashSet::new();
+        for experiment in in_datafile_order(&self.experiments, Experiment::position) {
+            if !keys.insert(experiment.key()) {
+                let path = format!("experiments[{}].key", experiment.position());
+                return Err(duplicate_key(path, experiment.key()));
+            }
+        }
+        for rollout in in_datafile_order(&self.rollouts, Rollout::position) {
+            for (index, rule) in rollout.experiments().iter().enumerate() {
+                if !keys.insert(rule.key()) {
+                    let path = format!("rollouts[{}].experiments[{index}].key", rollout.position());
+                    return Err(duplicate_key(path, rule.key()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get an experiment or a rule of a rollout by ID
+    ///
+    /// Experiments in groups are not evaluated, so they can not be found either.
+    pub fn experiment(&self, experiment_id: &str) -> Option<&Experiment> {
+        self.experiments.get(experiment_id).or_else(|| {
+            let (rollout_id, position) = self.rollout_rule_positions.get(experiment_id)?;
+            self.rollouts.get(rollout_id)?.experiments().get(*position)
+        })
+    }
+
+    /// Whether the experiment with the given ID is in a group
+    ///
+    /// Flags can refer to experiments in groups, which are skipped when deciding the flag.
+    fn is_grouped(&self, experiment_id: &str) -> bool {
+        self.groups.values().any(|group| {
+            group
+                .experiments()
+                .iter()
+                .any(|experiment| experiment.id() == experiment_id)
+        })
+    }
+
+    /// Get all experiments, including the rules of rollouts
+    pub fn all_experiments(&self) -> impl Iterator<Item = &Experiment> {
+        let rules = self
+            .rollouts
+            .values()
+            .flat_map(|rollout| rollout.experiments().iter());
+        self.experiments.values().chain(rules)
+    }
+
+    /// Get an experiment or a rule of a rollout by key
+    pub fn experiment_by_key(&self, experiment_key: &str) -> Option<&Experiment> {
+        self.experiment_ids_by_key
+            .get(experiment_key)
+            .and_then(|experiment_id| self.experiment(experiment_id))
+    }
+
     /// Getter for `account_id` field
     pub fn account_id(&self) -> &str {
         &self.account_id
     }
 
-    #[allow(dead_code)]
+    /// Getter for `version` field
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Getter for `project_id` field
     pub fn project_id(&self) -> &str {
         &self.project_id
     }
 
-    #[allow(dead_code)]
+    /// Getter for `environment_key` field
     pub fn environment_key(&self) -> &str {
         &self.environment_key
     }
@@ -69,19 +280,103 @@ impl Environment {
         self.anonymize_ip
     }
 
-    pub fn feature_flags(&self) -> &HashMap<String, FeatureFlag> {
-        &self.feature_flags
+    /// Getter for `region` field
+    pub fn region(&self) -> Region {
+        self.region
     }
 
-    pub fn experiments(&self) -> &HashMap<String, Experiment> {
-        &self.experiments
+    pub fn feature_flags(&self) -> &HashMap<Arc<str>, FeatureFlag> {
+        &self.feature_flags
     }
 
     pub fn rollouts(&self) -> &HashMap<String, Rollout> {
         &self.rollouts
     }
 
-    pub fn events(&self) -> &HashMap<String, Event> {
+    pub fn events(&self) -> &HashMap<Arc<str>, Event> {
         &self.events
     }
+
+    pub fn audiences(&self) -> &HashMap<String, Audience> {
+        &self.audiences
+    }
+
+    pub fn attributes(&self) -> &HashMap<String, Attribute> {
+        &self.attributes
+    }
+}
+
+/// Get the values of a map in the order in which they appeared in the datafile
+fn in_datafile_order<K, T>(map: &HashMap<K, T>, position: impl Fn(&T) -> usize) -> Vec<&T> {
+    let mut values = map.values().collect::<Vec<_>>();
+    values.sort_unstable_by_key(|value| position(value));
+    values
+}
+
+fn validate_traffic_allocation(experiment: &Experiment, path: String) -> Result<(), Report<DatafileError>> {
+    // An empty variation ID is used for traffic that is not allocated to any variation
+    match experiment
+        .traffic_allocation()
+        .variation_ids()
+        .find(|variation_id| !variation_id.is_empty() && experiment.variation(variation_id).is_none())
+    {
+        Some(variation_id) => Err(dangling_reference(path, variation_id)),
+        None => Ok(()),
+    }
+}
+
+fn dangling_reference(path: String, reference: &str) -> Report<DatafileError> {
+    report!(DatafileError::DanglingReference {
+        path,
+        reference: reference.into(),
+    })
+}
+
+fn duplicate_key(path: String, key: &str) -> Report<DatafileError> {
+    report!(DatafileError::DuplicateKey {
+        path,
+        key: key.into()
+    })
+}
+
+/// Built environment in a snapshot, including the lookup indices
+///
+/// Typed audiences are already merged into the audiences, so they are left out.
+#[cfg(feature = "snapshot")]
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Environment")]
+struct EnvironmentSnapshot {
+    version: u32,
+    account_id: String,
+    project_id: String,
+    environment_key: String,
+    revision: u32,
+    bot_filtering: bool,
+    anonymize_ip: bool,
+    region: Region,
+    events: HashMap<Arc<str>, Event>,
+    #[serde(with = "snapshot::map")]
+    experiments: HashMap<Arc<str>, Experiment>,
+    #[serde(with = "snapshot::map")]
+    rollouts: HashMap<String, Rollout>,
+    #[serde(with = "snapshot::map")]
+    groups: HashMap<String, Group>,
+    feature_flags: HashMap<Arc<str>, FeatureFlag>,
+    audiences: HashMap<String, Audience>,
+    #[serde(skip)]
+    typed_audiences: HashMap<String, Audience>,
+    attributes: HashMap<String, Attribute>,
+    experiment_ids_by_key: HashMap<Arc<str>, Arc<str>>,
+    rollout_rule_positions: HashMap<Arc<str>, (String, usize)>,
+}
+
+#[cfg(feature = "snapshot")]
+impl Snapshot for Environment {
+    fn serialize_snapshot<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EnvironmentSnapshot::serialize(self, serializer)
+    }
+
+    fn deserialize_snapshot<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        EnvironmentSnapshot::deserialize(deserializer)
+    }
 }
@@ -1,10 +1,82 @@
 // External imports
+use serde::de::Error as _;
+use serde_json::error::Category;
 use thiserror::Error;
 
+// Placeholder for the field name in the message of a missing field
+const FIELD_PLACEHOLDER: &str = "\u{0}";
+
 /// This type represents all possible errors that can occur when parsing the datafile
+///
+/// Errors about the content of the datafile include the JSON path at which the problem was found.
+/// The path identifies list items by their index in the datafile, like `featureFlags[0].rolloutId`.
 #[derive(Error, Debug, PartialEq)]
 pub enum DatafileError {
     #[doc(hidden)]
     #[error("JSON can not be parsed")]
     InvalidJson,
+    #[doc(hidden)]
+    #[error("Datafile can not be read")]
+    FailedRead,
+    #[doc(hidden)]
+    #[error("Datafile version {version} is not supported")]
+    UnsupportedVersion { version: u32 },
+    #[doc(hidden)]
+    #[error("Snapshot can not be loaded")]
+    InvalidSnapshot,
+    #[doc(hidden)]
+    #[error("Snapshot format version {version} is not supported")]
+    UnsupportedSnapshotVersion { version: u32 },
+    #[doc(hidden)]
+    #[error("Missing field at {path}")]
+    MissingField { path: String },
+    #[doc(hidden)]
+    #[error("Invalid type or value at {path}")]
+    InvalidType { path: String },
+    #[doc(hidden)]
+    #[error("Dangling reference to {reference} at {path}")]
+    DanglingReference { path: String, reference: String },
+    #[doc(hidden)]
+    #[error("Duplicate key {key} at {path}")]
+    DuplicateKey { path: String, key: String },
+}
+
+impl DatafileError {
+    /// Classify an error of the JSON deserializer, including the path at which it occurred
+    pub(crate) fn from_path_error(error: &serde_path_to_error::Error<serde_json::Error>) -> DatafileError {
+        // Syntax errors are not related to the structure of the datafile
+        match error.inner().classify() {
+            Category::Data => {}
+            Category::Io => return DatafileError::FailedRead,
+            Category::Syntax | Category::Eof => return DatafileError::InvalidJson,
+        }
+
+        // Serde reports a missing field at the path of the object that should contain it
+        let path = error.path().to_string();
+        match missing_field(error.inner()) {
+            Some(field) if path == "." => DatafileError::MissingField { path: field },
+            Some(field) => DatafileError::MissingField {
+                path: format!("{path}.{field}"),
+            },
+            None => DatafileError::InvalidType { path },
+        }
+    }
+}
+
+/// Get the name of the missing field, if the error is about a missing field
+///
+/// Serde JSON only reports missing fields as a message, so the message is compared with
+/// the message that Serde itself creates for a missing field, instead of a copy of its wording.
+fn missing_field(error: &serde_json::Error) -> Option<String> {
+    let template = serde_json::Error::missing_field(FIELD_PLACEHOLDER).to_string();
+    let (prefix, suffix) = template.split_once(FIELD_PLACEHOLDER)?;
+
+    // The message of the error also contains the position at which it occurred
+    let message = error.to_string();
+    let rest = message.strip_prefix(prefix)?;
+    let field = match suffix {
+        "" => rest.split(' ').next()?,
+        suffix => rest.split(suffix).next()?,
+    };
+    Some(field.into())
 }
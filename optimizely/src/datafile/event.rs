@@ -1,22 +1,25 @@
 // External imports
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Event {
-    id: String,
-    key: String,
+    id: Arc<str>,
+    key: Arc<str>,
+    #[serde(rename = "experimentIds", default)]
+    experiment_ids: Vec<String>,
 }
 
 impl Event {
     // Method to deserialize an array of Events into a Hashmap of Events
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Event>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<Arc<str>, Event>, D::Error>
     where
         D: Deserializer<'de>,
     {
         let mut map = HashMap::new();
         for event in Vec::<Event>::deserialize(deserializer)? {
-            map.insert(event.key.clone(), event);
+            map.insert(Arc::clone(&event.key), event);
         }
         Ok(map)
     }
@@ -27,8 +30,12 @@ impl Event {
     }
 
     /// Getter for `key` field
-    #[allow(dead_code)]
     pub fn key(&self) -> &str {
         &self.key
     }
+
+    /// Getter for `experiment_ids` field
+    pub fn experiment_ids(&self) -> &[String] {
+        &self.experiment_ids
+    }
 }
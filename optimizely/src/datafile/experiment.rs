@@ -1,31 +1,60 @@
 // External imports
-use serde::{Deserialize, Deserializer};
+#[cfg(feature = "snapshot")]
+use serde::Serializer;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 // Imports from super
-use super::{TrafficAllocation, Variation};
+#[cfg(feature = "snapshot")]
+use super::snapshot::{self, Snapshot};
+use super::{serialize_values, TrafficAllocation, Variation};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Experiment {
     #[serde()]
-    id: String,
+    id: Arc<str>,
+    #[serde()]
+    key: Arc<str>,
+    #[serde(rename = "audienceIds", default)]
+    audience_ids: Vec<String>,
+    #[serde(rename = "audienceConditions", default, skip_serializing_if = "Option::is_none")]
+    audience_conditions: Option<Value>,
     #[serde(rename = "layerId")]
-    campaign_id: String,
-    #[serde(rename = "trafficAllocation", deserialize_with = "TrafficAllocation::deserialize")]
+    campaign_id: Arc<str>,
+    #[serde(
+        rename = "trafficAllocation",
+        deserialize_with = "TrafficAllocation::deserialize",
+        serialize_with = "TrafficAllocation::serialize"
+    )]
     traffic_allocation: TrafficAllocation,
-    #[serde(rename = "variations", deserialize_with = "Variation::deserialize")]
-    variations: HashMap<String, Variation>,
+    #[serde(
+        rename = "variations",
+        deserialize_with = "Variation::deserialize",
+        serialize_with = "serialize_values"
+    )]
+    variations: HashMap<Arc<str>, Variation>,
+    #[serde(skip)]
+    variation_ids_by_key: HashMap<Arc<str>, Arc<str>>,
+    // Position in the list of the datafile, which is used in the path of errors
+    #[serde(skip)]
+    position: usize,
 }
 
 impl Experiment {
     // Method to deserialize an array of Experiments into a Hashmap of Experiments
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Experiment>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<Arc<str>, Experiment>, D::Error>
     where
         D: Deserializer<'de>,
     {
         let mut map = HashMap::new();
-        for experiment in Vec::<Experiment>::deserialize(deserializer)? {
-            map.insert(experiment.id.clone(), experiment);
+        for (position, mut experiment) in Vec::<Experiment>::deserialize(deserializer)?
+            .into_iter()
+            .enumerate()
+        {
+            experiment.position = position;
+            map.insert(Arc::clone(&experiment.id), experiment);
         }
         Ok(map)
     }
@@ -35,11 +64,41 @@ impl Experiment {
         &self.id
     }
 
+    /// Getter for `key` field
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Getter for `audience_ids` field
+    pub fn audience_ids(&self) -> &[String] {
+        &self.audience_ids
+    }
+
+    /// Getter for `audience_conditions` field
+    pub fn audience_conditions(&self) -> Option<&Value> {
+        self.audience_conditions.as_ref()
+    }
+
     #[allow(dead_code)]
     pub fn campaign_id(&self) -> &str {
         &self.campaign_id
     }
 
+    /// Shared `id` field, which can be cloned without allocating
+    pub fn shared_id(&self) -> &Arc<str> {
+        &self.id
+    }
+
+    /// Shared `key` field, which can be cloned without allocating
+    pub fn shared_key(&self) -> &Arc<str> {
+        &self.key
+    }
+
+    /// Shared `campaign_id` field, which can be cloned without allocating
+    pub fn shared_campaign_id(&self) -> &Arc<str> {
+        &self.campaign_id
+    }
+
     #[allow(dead_code)]
     pub fn traffic_allocation(&self) -> &TrafficAllocation {
         &self.traffic_allocation
@@ -48,4 +107,59 @@ impl Experiment {
     pub fn variation(&self, variation_id: &str) -> Option<&Variation> {
         self.variations.get(variation_id)
     }
+
+    /// Get the variation with the given key
+    pub fn variation_by_key(&self, variation_key: &str) -> Option<&Variation> {
+        self.variation_ids_by_key
+            .get(variation_key)
+            .and_then(|variation_id| self.variation(variation_id))
+    }
+
+    /// Index the variations by key, which is done once when building the datafile
+    pub fn build_index(&mut self) {
+        self.variation_ids_by_key = self
+            .variations
+            .values()
+            .map(|variation| (Arc::clone(variation.shared_key()), Arc::clone(variation.shared_id())))
+            .collect();
+    }
+
+    pub fn variations(&self) -> impl Iterator<Item = &Variation> {
+        self.variations.values()
+    }
+
+    /// Position of the experiment in the list of the datafile
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Built experiment in a snapshot, including the index of variations by key
+#[cfg(feature = "snapshot")]
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Experiment")]
+struct ExperimentSnapshot {
+    id: Arc<str>,
+    key: Arc<str>,
+    audience_ids: Vec<String>,
+    audience_conditions: Option<Value>,
+    campaign_id: Arc<str>,
+    #[serde(with = "snapshot::entity")]
+    traffic_allocation: TrafficAllocation,
+    variations: HashMap<Arc<str>, Variation>,
+    variation_ids_by_key: HashMap<Arc<str>, Arc<str>>,
+    // Only used for errors while validating a parsed datafile
+    #[serde(skip)]
+    position: usize,
+}
+
+#[cfg(feature = "snapshot")]
+impl Snapshot for Experiment {
+    fn serialize_snapshot<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExperimentSnapshot::serialize(self, serializer)
+    }
+
+    fn deserialize_snapshot<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ExperimentSnapshot::deserialize(deserializer)
+    }
 }
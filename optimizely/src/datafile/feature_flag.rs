@@ -1,37 +1,59 @@
 // External imports
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+// Imports from super
+use super::Variable;
 
 /// Optimizely feature flag.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct FeatureFlag {
     #[serde()]
-    key: String,
+    id: Arc<str>,
+    #[serde()]
+    key: Arc<str>,
     #[serde(rename = "rolloutId")]
     rollout_id: String,
     #[serde(rename = "experimentIds")]
     experiment_ids: Vec<String>,
-    // TODO: variables
+    #[serde(default)]
+    variables: Vec<Variable>,
+    // Position in the list of the datafile, which is used in the path of errors
+    #[serde(skip)]
+    position: usize,
 }
 
 impl FeatureFlag {
     // Method to deserialize an array of Rollouts into a Hashmap of Rollouts
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, FeatureFlag>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<Arc<str>, FeatureFlag>, D::Error>
     where
         D: Deserializer<'de>,
     {
         let mut map = HashMap::new();
-        for flag in Vec::<FeatureFlag>::deserialize(deserializer)? {
-            map.insert(flag.key.clone(), flag);
+        for (position, mut flag) in Vec::<FeatureFlag>::deserialize(deserializer)?
+            .into_iter()
+            .enumerate()
+        {
+            flag.position = position;
+            map.insert(Arc::clone(&flag.key), flag);
         }
         Ok(map)
     }
 
-    #[allow(dead_code)]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
     pub fn key(&self) -> &str {
         &self.key
     }
 
+    /// Shared `key` field, which can be cloned without allocating
+    pub fn shared_key(&self) -> &Arc<str> {
+        &self.key
+    }
+
     pub fn rollout_id(&self) -> &str {
         &self.rollout_id
     }
@@ -39,4 +61,13 @@ impl FeatureFlag {
     pub fn experiments_ids(&self) -> &Vec<String> {
         &self.experiment_ids
     }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    /// Position of the flag in the list of the datafile
+    pub fn position(&self) -> usize {
+        self.position
+    }
 }
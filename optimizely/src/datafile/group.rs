@@ -0,0 +1,87 @@
+// External imports
+#[cfg(feature = "snapshot")]
+use serde::Serializer;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+
+// Imports from super
+#[cfg(feature = "snapshot")]
+use super::snapshot::{self, Snapshot};
+use super::{Experiment, TrafficAllocation};
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Group {
+    id: String,
+    policy: String,
+    // The traffic of a group is allocated to experiments instead of variations
+    #[serde(
+        rename = "trafficAllocation",
+        deserialize_with = "TrafficAllocation::deserialize",
+        serialize_with = "TrafficAllocation::serialize"
+    )]
+    traffic_allocation: TrafficAllocation,
+    experiments: Vec<Experiment>,
+    // Position in the list of the datafile, which is used in the path of errors
+    #[serde(skip)]
+    position: usize,
+}
+
+impl Group {
+    // Method to deserialize an array of Groups into a Hashmap of Groups
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Group>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = HashMap::new();
+        for (position, mut group) in Vec::<Group>::deserialize(deserializer)?
+            .into_iter()
+            .enumerate()
+        {
+            group.position = position;
+            map.insert(group.id.clone(), group);
+        }
+        Ok(map)
+    }
+
+    /// Getter for `traffic_allocation` field
+    pub fn traffic_allocation(&self) -> &TrafficAllocation {
+        &self.traffic_allocation
+    }
+
+    /// Getter for `experiments` field
+    pub fn experiments(&self) -> &[Experiment] {
+        &self.experiments
+    }
+
+    /// Position of the group in the list of the datafile
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Built group in a snapshot, including the traffic allocation tree of its experiments
+#[cfg(feature = "snapshot")]
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Group")]
+struct GroupSnapshot {
+    id: String,
+    policy: String,
+    #[serde(with = "snapshot::entity")]
+    traffic_allocation: TrafficAllocation,
+    #[serde(with = "snapshot::vec")]
+    experiments: Vec<Experiment>,
+    // Only used for errors while validating a parsed datafile
+    #[serde(skip)]
+    position: usize,
+}
+
+#[cfg(feature = "snapshot")]
+impl Snapshot for Group {
+    fn serialize_snapshot<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GroupSnapshot::serialize(self, serializer)
+    }
+
+    fn deserialize_snapshot<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        GroupSnapshot::deserialize(deserializer)
+    }
+}
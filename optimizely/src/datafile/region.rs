@@ -0,0 +1,35 @@
+// External imports
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Data residency region of an Optimizely account
+///
+/// The region determines to which Event API endpoint the events are sent.
+/// Datafiles without a `region` field, or with a region that is unknown to this SDK, belong to the US region.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// Default region
+    #[default]
+    US,
+    /// European Union region
+    EU,
+}
+
+impl Region {
+    /// Deserialize the region of a datafile, using the default region for unknown regions
+    ///
+    /// New data residency regions should not make the whole datafile invalid.
+    pub(crate) fn deserialize_or_default<'de, D>(deserializer: D) -> Result<Region, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let region = String::deserialize(deserializer)?;
+        match region.as_str() {
+            "US" => Ok(Region::US),
+            "EU" => Ok(Region::EU),
+            _ => {
+                log::warn!("Unknown region {region:?}, using the default region instead");
+                Ok(Region::default())
+            }
+        }
+    }
+}
@@ -1,14 +1,21 @@
 // External imports
-use serde::{Deserialize, Deserializer};
+#[cfg(feature = "snapshot")]
+use serde::Serializer;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 // Imports from super
+#[cfg(feature = "snapshot")]
+use super::snapshot::{self, Snapshot};
 use super::Experiment;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Rollout {
     id: String,
     experiments: Vec<Experiment>,
+    // Position in the list of the datafile, which is used in the path of errors
+    #[serde(skip)]
+    position: usize,
 }
 
 impl Rollout {
@@ -18,7 +25,11 @@ impl Rollout {
         D: Deserializer<'de>,
     {
         let mut map = HashMap::new();
-        for rollout in Vec::<Rollout>::deserialize(deserializer)? {
+        for (position, mut rollout) in Vec::<Rollout>::deserialize(deserializer)?
+            .into_iter()
+            .enumerate()
+        {
+            rollout.position = position;
             map.insert(rollout.id.clone(), rollout);
         }
         Ok(map)
@@ -33,4 +44,37 @@ impl Rollout {
     pub fn experiments(&self) -> &Vec<Experiment> {
         &self.experiments
     }
+
+    pub fn experiments_mut(&mut self) -> &mut Vec<Experiment> {
+        &mut self.experiments
+    }
+
+    /// Position of the rollout in the list of the datafile
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Built rollout in a snapshot, of which the rules are built experiments
+#[cfg(feature = "snapshot")]
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Rollout")]
+struct RolloutSnapshot {
+    id: String,
+    #[serde(with = "snapshot::vec")]
+    experiments: Vec<Experiment>,
+    // Only used for errors while validating a parsed datafile
+    #[serde(skip)]
+    position: usize,
+}
+
+#[cfg(feature = "snapshot")]
+impl Snapshot for Rollout {
+    fn serialize_snapshot<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RolloutSnapshot::serialize(self, serializer)
+    }
+
+    fn deserialize_snapshot<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RolloutSnapshot::deserialize(deserializer)
+    }
 }
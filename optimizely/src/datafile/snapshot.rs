@@ -0,0 +1,198 @@
+// External imports
+use error_stack::{report, Result, ResultExt};
+use fasthash::murmur3::hash32 as murmur3_hash;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Imports from super
+use super::{Datafile, DatafileError, Environment};
+
+// Every snapshot starts with a header of these bytes, the format version and the checksum of the payload
+const MAGIC: &[u8; 8] = b"OPTZSNAP";
+const HEADER_LENGTH: usize = 16;
+
+/// Version of the snapshot format, which is increased whenever the layout of the payload changes
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+impl Datafile {
+    /// Write the datafile as a compact binary snapshot
+    ///
+    /// The snapshot contains a header with the format version and a checksum, followed by the datafile in MessagePack.
+    /// The payload contains the built datafile, including the lookup indices and the traffic allocation trees,
+    /// so loading a snapshot skips parsing JSON, building the indices and validating the references.
+    ///
+    /// ```
+    /// use optimizely::datafile::Datafile;
+    ///
+    /// let content = std::fs::read_to_string("../datafiles/sandbox.json")?;
+    /// let datafile = Datafile::build(&content)?;
+    ///
+    /// // Compile the datafile into a snapshot and load it again
+    /// let snapshot = datafile.to_snapshot()?;
+    /// let copy = Datafile::from_snapshot(&snapshot)?;
+    /// assert_eq!(copy.revision(), datafile.revision());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_snapshot(&self) -> Result<Vec<u8>, DatafileError> {
+        // Use field names, so a snapshot can be inspected with any MessagePack tool
+        let mut payload = Vec::new();
+        self.0
+            .serialize_snapshot(&mut rmp_serde::Serializer::new(&mut payload).with_struct_map())
+            .change_context(DatafileError::InvalidSnapshot)?;
+
+        let mut snapshot = Vec::with_capacity(HEADER_LENGTH + payload.len());
+        snapshot.extend_from_slice(MAGIC);
+        snapshot.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+        snapshot.extend_from_slice(&murmur3_hash(&payload).to_le_bytes());
+        snapshot.extend_from_slice(&payload);
+
+        Ok(snapshot)
+    }
+
+    /// Load a datafile from a binary snapshot that was written by [`Datafile::to_snapshot`]
+    ///
+    /// Snapshots of another format version, or of which the checksum does not match, are rejected.
+    /// The datafile was validated before it was written, so the references are not checked again.
+    pub fn from_snapshot(snapshot: &[u8]) -> Result<Datafile, DatafileError> {
+        if snapshot.len() < HEADER_LENGTH || &snapshot[..MAGIC.len()] != MAGIC {
+            return Err(report!(DatafileError::InvalidSnapshot)).attach_printable("Missing snapshot header");
+        }
+        let (header, payload) = snapshot.split_at(HEADER_LENGTH);
+
+        let version = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+        if version != SNAPSHOT_FORMAT_VERSION {
+            return Err(report!(DatafileError::UnsupportedSnapshotVersion { version }));
+        }
+
+        let checksum = u32::from_le_bytes([header[12], header[13], header[14], header[15]]);
+        if murmur3_hash(payload) != checksum {
+            return Err(report!(DatafileError::InvalidSnapshot)).attach_printable("Checksum does not match");
+        }
+
+        let environment = Environment::deserialize_snapshot(&mut rmp_serde::Deserializer::from_read_ref(payload))
+            .change_context(DatafileError::InvalidSnapshot)?;
+
+        // Only supported versions of the datafile format can be used
+        Datafile::check_version(environment.version())?;
+
+        Ok(Datafile(environment))
+    }
+}
+
+/// Entity that is written to a snapshot as built, instead of in the format of the datafile
+///
+/// The JSON format of an entity leaves out its lookup indices and stores maps as lists,
+/// so entities with built structures have a separate representation for snapshots.
+pub(super) trait Snapshot: Sized {
+    fn serialize_snapshot<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>;
+
+    fn deserialize_snapshot<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error>;
+}
+
+// Wrappers to use the snapshot representation of an entity inside a collection
+struct SnapshotRef<'a, T>(&'a T);
+
+struct SnapshotOf<T>(T);
+
+impl<T: Snapshot> Serialize for SnapshotRef<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.0.serialize_snapshot(serializer)
+    }
+}
+
+impl<'de, T: Snapshot> Deserialize<'de> for SnapshotOf<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        T::deserialize_snapshot(deserializer).map(SnapshotOf)
+    }
+}
+
+/// Snapshot representation of a single entity, used with `#[serde(with = "snapshot::entity")]`
+pub(super) mod entity {
+    // External imports
+    use serde::{Deserializer, Serializer};
+
+    // Imports from super
+    use super::Snapshot;
+
+    pub fn serialize<T: Snapshot, S: Serializer>(value: &T, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        value.serialize_snapshot(serializer)
+    }
+
+    pub fn deserialize<'de, T: Snapshot, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<T, D::Error> {
+        T::deserialize_snapshot(deserializer)
+    }
+}
+
+/// Snapshot representation of a list of entities, used with `#[serde(with = "snapshot::vec")]`
+pub(super) mod vec {
+    // External imports
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    // Imports from super
+    use super::{Snapshot, SnapshotOf, SnapshotRef};
+
+    pub fn serialize<T: Snapshot, S: Serializer>(values: &[T], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(SnapshotRef))
+    }
+
+    pub fn deserialize<'de, T: Snapshot, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Vec<T>, D::Error> {
+        let values = Vec::<SnapshotOf<T>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|SnapshotOf(value)| value).collect())
+    }
+}
+
+/// Snapshot representation of a map of entities, used with `#[serde(with = "snapshot::map")]`
+pub(super) mod map {
+    // External imports
+    use serde::de::{MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::hash::Hash;
+    use std::marker::PhantomData;
+
+    // Imports from super
+    use super::{Snapshot, SnapshotOf, SnapshotRef};
+
+    pub fn serialize<K, T, S>(map: &HashMap<K, T>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        T: Snapshot,
+        S: Serializer,
+    {
+        serializer.collect_map(map.iter().map(|(key, value)| (key, SnapshotRef(value))))
+    }
+
+    pub fn deserialize<'de, K, T, D>(deserializer: D) -> std::result::Result<HashMap<K, T>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        T: Snapshot,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+
+    // Insert the entities directly, instead of collecting the wrappers into a map first
+    struct MapVisitor<K, T>(PhantomData<(K, T)>);
+
+    impl<'de, K, T> Visitor<'de> for MapVisitor<K, T>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        T: Snapshot,
+    {
+        type Value = HashMap<K, T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map of entities")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> std::result::Result<Self::Value, A::Error> {
+            let mut map = HashMap::with_capacity(access.size_hint().unwrap_or_default());
+            while let Some((key, SnapshotOf(value))) = access.next_entry()? {
+                map.insert(key, value);
+            }
+            Ok(map)
+        }
+    }
+}
@@ -1,8 +1,12 @@
 // External imports
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
 
-#[derive(Deserialize, Debug)]
+// Imports from super
+#[cfg(feature = "snapshot")]
+use super::snapshot::Snapshot;
+
+#[derive(Deserialize, Serialize, Debug)]
 struct Range {
     #[serde(rename = "entityId")]
     variation_id: String,
@@ -10,7 +14,7 @@ struct Range {
     end: u64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct TrafficAllocation(BTreeMap<u64, String>);
 
 impl TrafficAllocation {
@@ -25,6 +29,22 @@ impl TrafficAllocation {
         Ok(TrafficAllocation(tree))
     }
 
+    pub fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Ranges are written in ascending order, just like in the original datafile
+        serializer.collect_seq(self.0.iter().map(|(end, variation_id)| Range {
+            variation_id: variation_id.clone(),
+            end: *end,
+        }))
+    }
+
+    /// Get the IDs of all variations that receive traffic
+    pub fn variation_ids(&self) -> impl Iterator<Item = &str> {
+        self.0.values().map(String::as_str)
+    }
+
     #[allow(dead_code)]
     pub fn variation(&self, bucket_value: u64) -> Option<&str> {
         // Use BTreeMap::range to find the variation in O(log(n))
@@ -35,6 +55,23 @@ impl TrafficAllocation {
     }
 }
 
+/// Built traffic allocation in a snapshot, which is the tree of ranges itself
+#[cfg(feature = "snapshot")]
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "TrafficAllocation")]
+struct TrafficAllocationSnapshot(BTreeMap<u64, String>);
+
+#[cfg(feature = "snapshot")]
+impl Snapshot for TrafficAllocation {
+    fn serialize_snapshot<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TrafficAllocationSnapshot::serialize(self, serializer)
+    }
+
+    fn deserialize_snapshot<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TrafficAllocationSnapshot::deserialize(deserializer)
+    }
+}
+
 /// Macro to create TrafficAllocation
 /// Currently only used for testing
 #[cfg(test)]
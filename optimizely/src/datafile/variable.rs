@@ -0,0 +1,40 @@
+// External imports
+use serde::{Deserialize, Serialize};
+
+/// A variable of a feature flag, with its default value
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Variable {
+    id: String,
+    key: String,
+    #[serde(rename = "type")]
+    variable_type: String,
+    #[serde(rename = "subType", default, skip_serializing_if = "Option::is_none")]
+    sub_type: Option<String>,
+    #[serde(rename = "defaultValue")]
+    default_value: String,
+}
+
+impl Variable {
+    /// Getter for `id` field
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Getter for `key` field
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Type of the variable, JSON variables are stored as a string with sub type `json`
+    pub fn variable_type(&self) -> &str {
+        match self.sub_type.as_deref() {
+            Some(sub_type) if self.variable_type == "string" => sub_type,
+            _ => &self.variable_type,
+        }
+    }
+
+    /// Getter for `default_value` field
+    pub fn default_value(&self) -> &str {
+        &self.default_value
+    }
+}
@@ -1,6 +1,7 @@
 // External imports
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A single variation like "off", "on" or other user-created variations.
 ///
@@ -9,25 +10,34 @@ use std::collections::HashMap;
 /// The `key` is a human-readable value.
 /// The value of `is_feature_enabled` is `false` for the "off" variation.
 /// All other variations will have `is_feature_enabled` is `true`.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Variation {
     #[serde()]
-    id: String,
+    id: Arc<str>,
     #[serde()]
-    key: String,
+    key: Arc<str>,
     #[serde(rename = "featureEnabled")]
     is_feature_enabled: bool,
+    #[serde(default)]
+    variables: Vec<VariableValue>,
+}
+
+/// Value of a flag variable within a variation
+#[derive(Debug, Deserialize, Serialize)]
+struct VariableValue {
+    id: String,
+    value: String,
 }
 
 impl Variation {
     // Method to deserialize an array of Variations into a Hashmap of Variations
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Variation>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<Arc<str>, Variation>, D::Error>
     where
         D: Deserializer<'de>,
     {
         let mut map = HashMap::new();
         for variation in Vec::<Variation>::deserialize(deserializer)? {
-            map.insert(variation.id.clone(), variation);
+            map.insert(Arc::clone(&variation.id), variation);
         }
         Ok(map)
     }
@@ -44,9 +54,27 @@ impl Variation {
         &self.key
     }
 
+    /// Shared `id` field, which can be cloned without allocating
+    pub fn shared_id(&self) -> &Arc<str> {
+        &self.id
+    }
+
+    /// Shared `key` field, which can be cloned without allocating
+    pub fn shared_key(&self) -> &Arc<str> {
+        &self.key
+    }
+
     /// Getter for `is_feature_enabled` field
     #[allow(dead_code)]
     pub fn is_feature_enabled(&self) -> bool {
         self.is_feature_enabled
     }
+
+    /// Get the value of the variable with the given ID, if it is overridden in this variation
+    pub fn variable_value(&self, variable_id: &str) -> Option<&str> {
+        self.variables
+            .iter()
+            .find(|variable| variable.id == variable_id)
+            .map(|variable| variable.value.as_ref())
+    }
 }
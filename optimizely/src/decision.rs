@@ -1,34 +1,66 @@
 //! Result of a feature flag
 
+// External imports
+use std::sync::{Arc, OnceLock};
+
 // Relative imports of sub modules
 pub use decide_options::DecideOptions;
 mod decide_options;
 
+// ID and key of the decision when the flag is off without being in any experiment or rollout
+const UNKNOWN_ID: &str = "-1";
+const OFF_VARIATION_KEY: &str = "off";
+
+// Shared copies of the ID and key above, so off decisions do not allocate them every time
+static UNKNOWN_ID_SHARED: OnceLock<Arc<str>> = OnceLock::new();
+static OFF_VARIATION_KEY_SHARED: OnceLock<Arc<str>> = OnceLock::new();
+
 /// Decision for a specific user and feature flag
+///
+/// The keys and IDs are shared with the datafile, so creating and cloning a decision does not copy any strings.
 #[derive(Debug, Clone)]
 pub struct Decision {
-    flag_key: String,
-    campaign_id: String,
-    experiment_id: String,
-    variation_id: String,
-    variation_key: String,
+    flag_key: Arc<str>,
+    campaign_id: Arc<str>,
+    experiment_id: Arc<str>,
+    variation_id: Arc<str>,
+    variation_key: Arc<str>,
     enabled: bool,
+    reasons: Vec<String>,
 }
 
 impl Decision {
-    pub(crate) fn new<T: Into<String>>(flag_key: T, campaign_id: T, experiment_id: T, variation_id: T, variation_key: T, enabled: bool) -> Decision {
+    pub(crate) fn new(
+        flag_key: Arc<str>, campaign_id: Arc<str>, experiment_id: Arc<str>, variation_id: Arc<str>,
+        variation_key: Arc<str>, enabled: bool,
+    ) -> Decision {
         Decision {
-            flag_key: flag_key.into(),
-            campaign_id: campaign_id.into(),
-            experiment_id: experiment_id.into(),
-            variation_id: variation_id.into(),
-            variation_key: variation_key.into(),
+            flag_key,
+            campaign_id,
+            experiment_id,
+            variation_id,
+            variation_key,
             enabled,
+            reasons: Vec::new(),
         }
     }
 
-    pub(crate) fn off(flag_key: &str) -> Decision {
-        Decision::new(flag_key, "-1", "-1", "-1", "off", false)
+    pub(crate) fn with_reasons(mut self, reasons: Vec<String>) -> Decision {
+        self.reasons = reasons;
+        self
+    }
+
+    pub(crate) fn off(flag_key: Arc<str>) -> Decision {
+        let unknown_id = UNKNOWN_ID_SHARED.get_or_init(|| Arc::from(UNKNOWN_ID));
+        let off_variation_key = OFF_VARIATION_KEY_SHARED.get_or_init(|| Arc::from(OFF_VARIATION_KEY));
+        Decision::new(
+            flag_key,
+            Arc::clone(unknown_id),
+            Arc::clone(unknown_id),
+            Arc::clone(unknown_id),
+            Arc::clone(off_variation_key),
+            false,
+        )
     }
 
     /// Get the flag key for which this decision was made
@@ -60,4 +92,9 @@ impl Decision {
     pub fn variation_id(&self) -> &str {
         &self.variation_id
     }
+
+    /// Get the reasons for this decision, only included when requested with the decide options
+    pub fn reasons(&self) -> &[String] {
+        &self.reasons
+    }
 }
@@ -28,7 +28,7 @@ use std::default::Default;
 ///
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct DecideOptions {
     /// Prevents the visitor from firing an impression while still being served the variation,
     /// which disables displaying results of the Decide method on the Optimizely application's Results page.
@@ -51,3 +51,16 @@ pub struct DecideOptions {
     /// Exclude flag variable values from the decision result. Use this option to minimize the returned decision by skipping large JSON variables.
     pub exclude_variables: bool,
 }
+
+impl DecideOptions {
+    /// Combine these options with other options, an option is set if it is set in either of them
+    pub(crate) fn merge(&self, other: &DecideOptions) -> DecideOptions {
+        DecideOptions {
+            disable_decision_event: self.disable_decision_event || other.disable_decision_event,
+            enabled_flags_only: self.enabled_flags_only || other.enabled_flags_only,
+            ignore_user_profile_service: self.ignore_user_profile_service || other.ignore_user_profile_service,
+            include_reasons: self.include_reasons || other.include_reasons,
+            exclude_variables: self.exclude_variables || other.exclude_variables,
+        }
+    }
+}
@@ -1,15 +1,32 @@
 //! Event logging to Optimizely Event API
+//!
+//! The event model and the [`EventDispatcher`] trait are available in all builds,
+//! so a custom transport can be used. The built-in dispatchers require the `online` feature.
 
 // Relative imports of sub modules
+#[cfg(feature = "async")]
+pub use async_event_dispatcher::AsyncEventDispatcher;
+#[cfg(feature = "online")]
 pub use batched_event_dispatcher::BatchedEventDispatcher;
+#[cfg(feature = "online")]
 pub use client::EventApiClient;
+pub use dispatch_error::DispatchError;
+#[cfg(feature = "online")]
 pub use error::EventApiError;
+#[cfg(feature = "online")]
 pub use simple_event_dispatcher::SimpleEventDispatcher;
 pub use trait_event_dispatcher::EventDispatcher;
 
+#[cfg(feature = "async")]
+mod async_event_dispatcher;
+#[cfg(feature = "online")]
 mod batched_event_dispatcher;
+#[cfg(feature = "online")]
 mod client;
+mod dispatch_error;
+#[cfg(feature = "online")]
 mod error;
 pub mod request;
+#[cfg(feature = "online")]
 mod simple_event_dispatcher;
 mod trait_event_dispatcher;
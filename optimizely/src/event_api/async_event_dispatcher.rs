@@ -0,0 +1,165 @@
+// External imports
+use error_stack::{report, Result, ResultExt};
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+// Imports from super
+use super::batched_event_dispatcher::{EventEnum, ThreadMessage, DEFAULT_BATCH_THRESHOLD, DEFAULT_QUEUE_CAPACITY};
+use super::{request::Payload, DispatchError, EventApiClient, EventDispatcher};
+
+// Imports from crate
+use crate::{client::UserContext, Conversion, Decision};
+
+// Maximum time that the first event of an incomplete batch waits before the batch is sent
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Implementation of the EventDispatcher trait that collects events in a task on a Tokio runtime
+///
+/// Sending an event to the dispatcher never blocks, so it is safe to make decisions within async code.
+/// Events wait in a bounded queue, when the queue is full the event is rejected with `DispatchError::QueueFull`.
+/// A batch is sent when it reaches the batch threshold or a second after its first event, whichever comes first.
+/// The HTTP client is blocking, so each request is made on a thread where blocking is acceptable.
+///
+/// The task of the dispatcher is spawned on the runtime of the given handle.
+/// When the dispatcher is dropped, the remaining events are sent as long as the runtime is running.
+///
+/// ```
+/// use optimizely::{event_api::AsyncEventDispatcher, Client};
+/// use tokio::runtime::Handle;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // Initialize Optimizely client with an event dispatcher on the current runtime
+/// let file_path = "../datafiles/sandbox.json";
+/// let optimizely_client = Client::from_local_datafile(file_path)?
+///     .with_event_dispatcher(AsyncEventDispatcher::new(Handle::current()))
+///     .initialize();
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncEventDispatcher {
+    transmitter: mpsc::Sender<ThreadMessage>,
+}
+
+impl AsyncEventDispatcher {
+    /// Constructor for a new async event dispatcher on the runtime of the given handle
+    pub fn new(runtime: Handle) -> AsyncEventDispatcher {
+        AsyncEventDispatcher::with_capacity(runtime, DEFAULT_QUEUE_CAPACITY)
+    }
+
+    /// Constructor for a new async event dispatcher with a custom queue capacity
+    ///
+    /// # Panics
+    ///
+    /// Panics when the capacity is zero.
+    pub fn with_capacity(runtime: Handle, capacity: usize) -> AsyncEventDispatcher {
+        assert!(capacity > 0, "queue capacity must be greater than zero");
+        let (transmitter, mut receiver) = mpsc::channel::<ThreadMessage>(capacity);
+
+        // Receiver logic in separate task
+        runtime.spawn(async move {
+            let mut payload_option = Option::None;
+            let mut api_client_option = Option::None;
+            let mut deadline_option = Option::None;
+
+            loop {
+                // Wait for the next message, but send the incomplete batch when its deadline has passed
+                let message_option = match deadline_option {
+                    Some(deadline) => match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                        Ok(message_option) => message_option,
+                        Err(_) => {
+                            deadline_option = None;
+                            AsyncEventDispatcher::send(payload_option.take(), &api_client_option).await;
+                            continue;
+                        }
+                    },
+                    None => receiver.recv().await,
+                };
+                let Some(message) = message_option else {
+                    break;
+                };
+
+                // Deconstruct the message
+                let ThreadMessage {
+                    account_id,
+                    user_id,
+                    event,
+                    api_client,
+                } = message;
+
+                // Events are sent with the API client of the latest message
+                api_client_option = Some(api_client);
+
+                // Use existing payload or create new one, which is sent at the latest after the flush interval
+                let payload = payload_option.get_or_insert_with(|| {
+                    deadline_option = Some(Instant::now() + DEFAULT_FLUSH_INTERVAL);
+                    Payload::new(account_id)
+                });
+
+                // the corresponding event to the payload
+                match event {
+                    EventEnum::Conversion(conversion) => {
+                        payload.add_conversion_event(&user_id, &conversion);
+                    }
+                    EventEnum::Decision(decision) => {
+                        payload.add_decision_event(&user_id, &decision);
+                    }
+                }
+
+                // Send payload if reached the batch threshold
+                if payload.size() >= DEFAULT_BATCH_THRESHOLD {
+                    log::debug!("Reached DEFAULT_BATCH_THRESHOLD");
+                    deadline_option = None;
+                    AsyncEventDispatcher::send(payload_option.take(), &api_client_option).await;
+                }
+            }
+
+            // The transmitter has been dropped, so send the remaining events
+            AsyncEventDispatcher::send(payload_option, &api_client_option).await;
+        });
+
+        AsyncEventDispatcher { transmitter }
+    }
+
+    fn transmit(&self, user_context: &UserContext, event: EventEnum) -> Result<(), DispatchError> {
+        // Build message
+        let message = ThreadMessage::new(user_context, event);
+
+        // Send message to task without blocking
+        match self.transmitter.try_send(message) {
+            Ok(_) => {
+                log::debug!("Successfully sent message to task");
+                Ok(())
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                Err(report!(DispatchError::QueueFull)).attach_printable("Queue of task is full")
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                Err(report!(DispatchError::Closed)).attach_printable("Failed to send message to task")
+            }
+        }
+    }
+
+    async fn send(payload_option: Option<Payload<'static>>, api_client_option: &Option<EventApiClient>) {
+        // Nothing to send if there is no payload
+        let (Some(payload), Some(api_client)) = (payload_option, api_client_option.clone()) else {
+            return;
+        };
+
+        // Ignore result, since failures are already logged
+        let result = tokio::task::spawn_blocking(move || payload.send(&api_client)).await;
+        drop(result);
+    }
+}
+
+impl EventDispatcher for AsyncEventDispatcher {
+    fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion) -> Result<(), DispatchError> {
+        self.transmit(user_context, EventEnum::Conversion(conversion))
+    }
+
+    fn send_decision_event(&self, user_context: &UserContext, decision: Decision) -> Result<(), DispatchError> {
+        self.transmit(user_context, EventEnum::Decision(decision))
+    }
+}
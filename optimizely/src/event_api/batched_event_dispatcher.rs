@@ -1,50 +1,96 @@
 // External imports
+use error_stack::{report, Result, ResultExt};
 use std::sync::mpsc;
 use std::thread;
 
 // Imports from super
-use super::{request::Payload, EventDispatcher};
+use super::{request::Payload, DispatchError, EventApiClient, EventDispatcher};
 
 // Imports from crate
 use crate::{client::UserContext, Conversion, Decision};
 
 // Structure used to send message between threads
-struct ThreadMessage {
-    account_id: String,
-    user_id: String,
-    event: EventEnum,
+pub(super) struct ThreadMessage {
+    pub(super) account_id: String,
+    pub(super) api_client: EventApiClient,
+    pub(super) user_id: String,
+    pub(super) event: EventEnum,
 }
-enum EventEnum {
+
+impl ThreadMessage {
+    pub(super) fn new(user_context: &UserContext, event: EventEnum) -> ThreadMessage {
+        // Create a String so the value can be owned by the other thread.
+        let account_id = user_context.datafile().account_id().into();
+        let api_client = user_context.client().event_api_client();
+        let user_id = user_context.user_id().into();
+
+        ThreadMessage {
+            account_id,
+            api_client,
+            user_id,
+            event,
+        }
+    }
+}
+
+pub(super) enum EventEnum {
     Conversion(Conversion),
     Decision(Decision),
 }
 
 // Upper limit to number of events in a batch
-const DEFAULT_BATCH_THRESHOLD: usize = 10;
+pub(super) const DEFAULT_BATCH_THRESHOLD: usize = 10;
+
+// Upper limit to number of events waiting to be added to a batch
+pub(super) const DEFAULT_QUEUE_CAPACITY: usize = 1_000;
 
 /// Implementation of the EventDispatcher trait that collects multiple events before sending them
 ///
+/// Events wait in a bounded queue until the background thread adds them to a batch.
+/// When the queue is full, the event is rejected with `DispatchError::QueueFull` instead of blocking.
+///
 /// TODO: add example usage in SDK
 ///
 /// Inspiration from [Spawn threads and join in destructor](https://users.rust-lang.org/t/spawn-threads-and-join-in-destructor/1613/9)
 pub struct BatchedEventDispatcher {
     thread_handle: Option<thread::JoinHandle<()>>,
-    transmitter: Option<mpsc::Sender<ThreadMessage>>,
+    transmitter: Option<mpsc::SyncSender<ThreadMessage>>,
 }
 
 impl Default for BatchedEventDispatcher {
     /// Constructor for a new batched event dispatcher
     fn default() -> BatchedEventDispatcher {
-        let (transmitter, receiver) = mpsc::channel::<ThreadMessage>();
+        BatchedEventDispatcher::with_capacity(DEFAULT_QUEUE_CAPACITY)
+    }
+}
+
+impl BatchedEventDispatcher {
+    /// Constructor for a new batched event dispatcher with a custom queue capacity
+    ///
+    /// # Panics
+    ///
+    /// Panics when the capacity is zero.
+    pub fn with_capacity(capacity: usize) -> BatchedEventDispatcher {
+        assert!(capacity > 0, "queue capacity must be greater than zero");
+        let (transmitter, receiver) = mpsc::sync_channel::<ThreadMessage>(capacity);
 
         // Receiver logic in separate thread
         let thread_handle = thread::spawn(move || {
             let mut payload_option = Option::None;
+            let mut api_client_option = Option::None;
 
             // Keep receiving new messages from the main thread
             for message in receiver.iter() {
                 // Deconstruct the message
-                let ThreadMessage { account_id, user_id, event } = message;
+                let ThreadMessage {
+                    account_id,
+                    user_id,
+                    event,
+                    api_client,
+                } = message;
+
+                // Events are sent with the API client of the latest message
+                let api_client = api_client_option.insert(api_client);
 
                 // Use existing payload or create new one
                 let payload = payload_option.get_or_insert_with(|| Payload::new(account_id));
@@ -53,18 +99,23 @@ impl Default for BatchedEventDispatcher {
                 match event {
                     EventEnum::Conversion(conversion) => {
                         payload.add_conversion_event(&user_id, &conversion);
-                    },
+                    }
                     EventEnum::Decision(decision) => {
                         payload.add_decision_event(&user_id, &decision);
-                    },
+                    }
                 }
 
-                // Send payload if reached the batch threshold
+                // Send payload if reached the batch threshold, failures are already logged
                 if let Some(payload) = payload_option.take_if(|payload| payload.size() >= DEFAULT_BATCH_THRESHOLD) {
                     log::debug!("Reached DEFAULT_BATCH_THRESHOLD");
-                    payload.send();
+                    drop(payload.send(api_client));
                 }
             }
+
+            // The transmitter has been dropped, so send the remaining events
+            if let (Some(payload), Some(api_client)) = (payload_option, api_client_option) {
+                drop(payload.send(&api_client));
+            }
         });
 
         BatchedEventDispatcher {
@@ -72,6 +123,28 @@ impl Default for BatchedEventDispatcher {
             transmitter: Some(transmitter),
         }
     }
+
+    fn transmit(&self, user_context: &UserContext, event: EventEnum) -> Result<(), DispatchError> {
+        // Build message
+        let message = ThreadMessage::new(user_context, event);
+
+        // Send message to thread without blocking
+        match &self.transmitter {
+            Some(tx) => match tx.try_send(message) {
+                Ok(_) => {
+                    log::debug!("Successfully sent message to thread");
+                    Ok(())
+                }
+                Err(mpsc::TrySendError::Full(_)) => {
+                    Err(report!(DispatchError::QueueFull)).attach_printable("Queue of thread is full")
+                }
+                Err(mpsc::TrySendError::Disconnected(_)) => {
+                    Err(report!(DispatchError::Closed)).attach_printable("Failed to send message to thread")
+                }
+            },
+            None => Err(report!(DispatchError::Closed)).attach_printable("Transmitter already dropped"),
+        }
+    }
 }
 
 impl Drop for BatchedEventDispatcher {
@@ -93,41 +166,11 @@ impl Drop for BatchedEventDispatcher {
 }
 
 impl EventDispatcher for BatchedEventDispatcher {
-    fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion) {
+    fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion) -> Result<(), DispatchError> {
         self.transmit(user_context, EventEnum::Conversion(conversion))
     }
 
-    fn send_decision_event(&self, user_context: &UserContext, decision: Decision) {
+    fn send_decision_event(&self, user_context: &UserContext, decision: Decision) -> Result<(), DispatchError> {
         self.transmit(user_context, EventEnum::Decision(decision))
     }
 }
-
-impl BatchedEventDispatcher {
-    fn transmit(&self, user_context: &UserContext, event: EventEnum) {
-        // Create a String so the value can be owned by the other thread.
-        let account_id = user_context.client().datafile().account_id().into();
-        let user_id = user_context.user_id().into();
-
-        // Build message
-        let message = ThreadMessage {
-            account_id,
-            user_id,
-            event,
-        };
-
-        // Send message to thread
-        match &self.transmitter {
-            Some(tx) => match tx.send(message) {
-                Ok(_) => {
-                    log::debug!("Successfully sent message to thread");
-                }
-                Err(_) => {
-                    log::error!("Failed to send message to thread");
-                }
-            },
-            None => {
-                log::error!("Transmitter already dropped");
-            }
-        }
-    }
-}
\ No newline at end of file
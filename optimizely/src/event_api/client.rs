@@ -1,29 +1,96 @@
 // External imports
-use error_stack::{Result, ResultExt};
+use error_stack::{report, Result, ResultExt};
+use std::sync::Arc;
 
 // Imports from super
 use super::{request::Payload, EventApiError};
 
+// Imports from crate
+use crate::datafile::Region;
+use crate::http::{HttpClient, UreqHttpClient};
+use crate::notification::{LogEventNotification, NotificationCenter};
+
 // Information about the API endpoint
-const ENDPOINT_URL: &str = "https://logx.optimizely.com/v1/events";
+const US_ENDPOINT_URL: &str = "https://logx.optimizely.com/v1/events";
+const EU_ENDPOINT_URL: &str = "https://eu.logx.optimizely.com/v1/events";
 const CONTENT_TYPE_KEY: &str = "content-type";
 const CONTENT_TYPE_VALUE: &str = "application/json";
 
 /// HTTP client for the Event API
-pub struct EventApiClient {}
+#[derive(Clone)]
+pub struct EventApiClient {
+    endpoint: String,
+    http_client: Arc<dyn HttpClient>,
+    notification_center: Option<Arc<NotificationCenter>>,
+}
 
 impl EventApiClient {
+    /// Construct a client that sends requests to a custom endpoint
+    pub fn new<T: Into<String>>(endpoint: T) -> EventApiClient {
+        EventApiClient {
+            endpoint: endpoint.into(),
+            http_client: Arc::new(UreqHttpClient::default()),
+            notification_center: None,
+        }
+    }
+
+    /// Use a custom HTTP client to send requests
+    pub fn with_http_client(mut self, http_client: Arc<dyn HttpClient>) -> EventApiClient {
+        self.http_client = http_client;
+        self
+    }
+
+    /// Notify the log event listeners of the notification center before every request
+    pub fn with_notification_center(mut self, notification_center: Arc<NotificationCenter>) -> EventApiClient {
+        self.notification_center = Some(notification_center);
+        self
+    }
+
+    /// Construct a client that sends requests to the endpoint of the given region
+    pub fn for_region(region: Region) -> EventApiClient {
+        EventApiClient::new(EventApiClient::region_endpoint(region))
+    }
+
+    /// Get the default endpoint URL of the given region
+    pub fn region_endpoint(region: Region) -> &'static str {
+        match region {
+            Region::US => US_ENDPOINT_URL,
+            Region::EU => EU_ENDPOINT_URL,
+        }
+    }
+
+    /// Get the endpoint URL to which requests are sent
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
     /// Serialize the payload to JSON and send to Event API
-    pub fn send(payload: &Payload) -> Result<(), EventApiError> {
+    pub fn send(&self, payload: &Payload) -> Result<(), EventApiError> {
         // Convert to JSON document and dump as String
         let body = serde_json::to_string(payload).change_context(EventApiError::FailedSerialize)?;
 
+        // Notify listeners about the outgoing request
+        if let Some(notification_center) = &self.notification_center {
+            let notification = LogEventNotification {
+                endpoint: &self.endpoint,
+                body: &body,
+            };
+            notification_center.notify_log_event(&notification);
+        }
+
         // Make POST request
-        ureq::post(ENDPOINT_URL)
-            .set(CONTENT_TYPE_KEY, CONTENT_TYPE_VALUE)
-            .send_string(&body)
+        let headers = [(CONTENT_TYPE_KEY, CONTENT_TYPE_VALUE)];
+        let response = self
+            .http_client
+            .post(&self.endpoint, &headers, &body)
             .change_context(EventApiError::FailedRequest)?;
 
+        // Any response other than 2xx means the events were not accepted
+        if !response.is_success() {
+            return Err(report!(EventApiError::FailedRequest))
+                .attach_printable(format!("Unexpected status code {}", response.status()));
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,16 @@
+// External imports
+use thiserror::Error;
+
+/// This type represents all possible errors that can occur when handing an event to an event dispatcher
+#[derive(Error, Debug, PartialEq)]
+pub enum DispatchError {
+    #[doc(hidden)]
+    #[error("Event dispatcher queue is full")]
+    QueueFull,
+    #[doc(hidden)]
+    #[error("Event dispatcher is closed")]
+    Closed,
+    #[doc(hidden)]
+    #[error("Event dispatcher failed to send the event")]
+    SendFailed,
+}
@@ -3,19 +3,27 @@ use serde::Serialize;
 use std::{collections::HashMap, time::SystemTime};
 use uuid::Uuid;
 
+// Imports from crate
+use crate::conversion::EventTags;
+
 #[derive(Serialize)]
 pub struct Event {
     uuid: String,
     timestamp: u128,
     entity_id: String,
     key: String,
-    tags: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revenue: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<f64>,
+    tags: EventTags,
     properties: HashMap<String, String>,
 }
 
 impl Event {
     pub fn new(
-        entity_id: String, key: String, properties: HashMap<String, String>, tags: HashMap<String, String>,
+        entity_id: String, key: String, properties: HashMap<String, String>, tags: EventTags, revenue: Option<i64>,
+        value: Option<f64>,
     ) -> Event {
         // Generate new UUID
         let uuid = Uuid::new_v4().as_hyphenated().to_string();
@@ -31,6 +39,8 @@ impl Event {
             timestamp,
             entity_id,
             key,
+            revenue,
+            value,
             properties,
             tags,
         }
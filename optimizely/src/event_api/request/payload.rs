@@ -1,10 +1,15 @@
 // External imports
+#[cfg(feature = "online")]
+use error_stack::Result;
 use serde::Serialize;
 use std::collections::HashMap;
 
 // Imports from super
 use super::Visitor;
-use crate::{event_api::EventApiClient, Conversion, Decision};
+use crate::conversion::EventTags;
+#[cfg(feature = "online")]
+use crate::event_api::{EventApiClient, EventApiError};
+use crate::{Conversion, Decision};
 
 // Information regarding the SDK client
 const CLIENT_NAME: &str = "rust-sdk";
@@ -73,7 +78,7 @@ impl Payload<'_> {
 
         // Campaign activated event does not have tags or properties
         let properties = HashMap::default();
-        let tags = HashMap::default();
+        let tags = EventTags::default();
 
         // Add campaign_activated event
         let conversion = Conversion::new(ACTIVATE_EVENT_KEY, entity_id, properties, tags);
@@ -83,13 +88,17 @@ impl Payload<'_> {
         self.visitors.push(visitor);
     }
 
-    /// Send entire payload
-    pub fn send(&self) {
+    /// Send entire payload using the given Event API client
+    ///
+    /// Failures are logged and returned.
+    #[cfg(feature = "online")]
+    pub fn send(&self, api_client: &EventApiClient) -> Result<(), EventApiError> {
         // Sending payload
         log::debug!("Sending request to Event API");
 
         // Send payload to endpoint
-        match EventApiClient::send(self) {
+        let result = api_client.send(self);
+        match &result {
             Ok(_) => {
                 log::info!("Successfully sent request to Event API");
             }
@@ -98,14 +107,6 @@ impl Payload<'_> {
                 log::error!("\n{report:?}");
             }
         }
-    }
-}
-
-impl Drop for Payload<'_> {
-    fn drop(&mut self) {
-        log::debug!("Dropping Payload");
-
-        // If the Payload is dropped, make one last request to the Event API
-        self.send()
+        result
     }
 }
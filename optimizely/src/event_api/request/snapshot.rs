@@ -34,6 +34,8 @@ impl Snapshot {
             conversion.event_key().into(),
             conversion.properties().clone(),
             conversion.tags().clone(),
+            conversion.revenue(),
+            conversion.value(),
         );
         self.events.push(event);
     }
@@ -1,9 +1,14 @@
+// External imports
+use error_stack::{Result, ResultExt};
+
 // Imports from super
-use super::{request::Payload, EventDispatcher};
+use super::{request::Payload, DispatchError, EventDispatcher};
 use crate::{client::UserContext, Conversion, Decision};
 
 /// Implementation of the EventDispatcher trait that makes an HTTP request for every event
 ///
+/// The request is made before returning, so a failed request is returned as `DispatchError::SendFailed`.
+///
 /// TODO: add example usage in SDK
 pub struct SimpleEventDispatcher {}
 
@@ -15,29 +20,33 @@ impl Default for SimpleEventDispatcher {
 }
 
 impl EventDispatcher for SimpleEventDispatcher {
-    fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion) {
+    fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion) -> Result<(), DispatchError> {
         log::debug!("Sending conversion event to Event API");
 
         // Generate a new payload
-        let mut payload = Payload::new(user_context.client().datafile().account_id());
+        let mut payload = Payload::new(user_context.datafile().account_id());
 
         // Add single conversion
         payload.add_conversion_event(user_context.user_id(), &conversion);
 
         // Dispatch single conversion
-        payload.send()
+        payload
+            .send(&user_context.client().event_api_client())
+            .change_context(DispatchError::SendFailed)
     }
 
-    fn send_decision_event(&self, user_context: &UserContext, decision: Decision) {
+    fn send_decision_event(&self, user_context: &UserContext, decision: Decision) -> Result<(), DispatchError> {
         log::debug!("Sending decision event to Event API");
 
         // Generate a new payload
-        let mut payload = Payload::new(user_context.client().datafile().account_id());
+        let mut payload = Payload::new(user_context.datafile().account_id());
 
         // Add single decision
         payload.add_decision_event(user_context.user_id(), &decision);
 
         // Dispatch single decision
-        payload.send()
+        payload
+            .send(&user_context.client().event_api_client())
+            .change_context(DispatchError::SendFailed)
     }
 }
@@ -1,14 +1,63 @@
+// External imports
+use error_stack::Result;
+
 // Imports from super
+use super::DispatchError;
 use crate::{client::UserContext, Conversion, Decision};
 
 /// Trait for sending events to Optimizely Event API
 ///
-/// It is possible to make a custom event dispatcher by implementing this trait
-/// TODO: add example again
-pub trait EventDispatcher {
+/// It is possible to make a custom event dispatcher by implementing this trait,
+/// for example to send events using a different transport.
+///
+/// ```
+/// use error_stack::Result;
+/// use optimizely::event_api::{request::Payload, DispatchError, EventDispatcher};
+/// use optimizely::{client::UserContext, Client, Conversion, Decision};
+///
+/// // Event dispatcher that writes every payload as JSON to standard output
+/// struct StdoutEventDispatcher;
+///
+/// impl StdoutEventDispatcher {
+///     fn write(&self, payload: &Payload) {
+///         println!("{}", serde_json::to_string(payload).unwrap());
+///     }
+/// }
+///
+/// impl EventDispatcher for StdoutEventDispatcher {
+///     fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion) -> Result<(), DispatchError> {
+///         let mut payload = Payload::new(user_context.datafile().account_id());
+///         payload.add_conversion_event(user_context.user_id(), &conversion);
+///         self.write(&payload);
+///         Ok(())
+///     }
+///
+///     fn send_decision_event(&self, user_context: &UserContext, decision: Decision) -> Result<(), DispatchError> {
+///         let mut payload = Payload::new(user_context.datafile().account_id());
+///         payload.add_decision_event(user_context.user_id(), &decision);
+///         self.write(&payload);
+///         Ok(())
+///     }
+/// }
+///
+/// // Initialize Optimizely client using local datafile and custom event dispatcher
+/// let file_path = "../datafiles/sandbox.json";
+/// let optimizely_client = Client::from_local_datafile(file_path)?
+///     .with_event_dispatcher(StdoutEventDispatcher)
+///     .initialize();
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// The client can be shared between threads, so an event dispatcher has to be `Send` and `Sync` as well.
+///
+/// An error is returned when the dispatcher could not accept the event, for example because its queue is full.
+/// Dispatchers that send the event before returning also return failures while sending,
+/// dispatchers that send events in the background can only log those failures.
+pub trait EventDispatcher: Send + Sync {
     /// Send conversion event to destination
-    fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion);
+    fn send_conversion_event(&self, user_context: &UserContext, conversion: Conversion) -> Result<(), DispatchError>;
 
     /// Send event to destination
-    fn send_decision_event(&self, user_context: &UserContext, decision: Decision);
+    fn send_decision_event(&self, user_context: &UserContext, decision: Decision) -> Result<(), DispatchError>;
 }
@@ -0,0 +1,12 @@
+//! HTTP transport used for downloading datafiles and sending events
+
+// Relative imports of sub modules
+pub use error::HttpError;
+pub use response::HttpResponse;
+pub use trait_http_client::HttpClient;
+pub use ureq_http_client::UreqHttpClient;
+
+mod error;
+mod response;
+mod trait_http_client;
+mod ureq_http_client;
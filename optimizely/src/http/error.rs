@@ -0,0 +1,13 @@
+// External imports
+use thiserror::Error;
+
+/// This type represents all possible errors that can occur when making an HTTP request
+#[derive(Error, Debug, PartialEq)]
+pub enum HttpError {
+    #[doc(hidden)]
+    #[error("Failed to make HTTP request")]
+    FailedRequest,
+    #[doc(hidden)]
+    #[error("Failed to read HTTP response")]
+    FailedResponse,
+}
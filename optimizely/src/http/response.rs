@@ -0,0 +1,54 @@
+/// Response to an HTTP request
+///
+/// The status code is not interpreted by the HTTP client, so any status code can be returned.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl HttpResponse {
+    /// Construct a new response with a status code and body
+    pub fn new<T: Into<String>>(status: u16, body: T) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Add a header to the response
+    pub fn with_header<T: Into<String>>(mut self, name: T, value: T) -> HttpResponse {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Get the status code
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Whether the status code is in the 2xx range
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Get the value of a header, the name is case-insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the body
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Take ownership of the body
+    pub fn into_body(self) -> String {
+        self.body
+    }
+}
@@ -0,0 +1,34 @@
+// External imports
+use error_stack::Result;
+
+// Imports from super
+use super::{HttpError, HttpResponse};
+
+/// Trait for making HTTP requests
+///
+/// It is possible to use a custom HTTP stack, or a fake for testing, by implementing this trait.
+/// Responses with any status code should be returned as `Ok`, errors are reserved for failed requests.
+///
+/// ```
+/// use error_stack::Result;
+/// use optimizely::http::{HttpClient, HttpError, HttpResponse};
+///
+/// struct OfflineHttpClient;
+///
+/// impl HttpClient for OfflineHttpClient {
+///     fn get(&self, _url: &str, _headers: &[(&str, &str)]) -> Result<HttpResponse, HttpError> {
+///         Ok(HttpResponse::new(404, ""))
+///     }
+///
+///     fn post(&self, _url: &str, _headers: &[(&str, &str)], _body: &str) -> Result<HttpResponse, HttpError> {
+///         Ok(HttpResponse::new(204, ""))
+///     }
+/// }
+/// ```
+pub trait HttpClient: Send + Sync {
+    /// Make a GET request with the given headers
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<HttpResponse, HttpError>;
+
+    /// Make a POST request with the given headers and body
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<HttpResponse, HttpError>;
+}
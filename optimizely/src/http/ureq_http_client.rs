@@ -0,0 +1,60 @@
+// External imports
+use error_stack::{Report, Result, ResultExt};
+
+// Imports from super
+use super::{HttpClient, HttpError, HttpResponse};
+
+/// Implementation of the HttpClient trait using the `ureq` library
+#[derive(Default)]
+pub struct UreqHttpClient {}
+
+impl HttpClient for UreqHttpClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<HttpResponse, HttpError> {
+        let request = headers
+            .iter()
+            .fold(ureq::get(url), |request, (name, value)| request.set(name, value));
+
+        UreqHttpClient::convert(request.call())
+    }
+
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<HttpResponse, HttpError> {
+        let request = headers
+            .iter()
+            .fold(ureq::post(url), |request, (name, value)| request.set(name, value));
+
+        UreqHttpClient::convert(request.send_string(body))
+    }
+}
+
+impl UreqHttpClient {
+    fn convert(result: std::result::Result<ureq::Response, ureq::Error>) -> Result<HttpResponse, HttpError> {
+        // Ureq returns an error for 4xx and 5xx responses, but those are still valid responses
+        let response = match result {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(error) => return Err(Report::new(error).change_context(HttpError::FailedRequest)),
+        };
+
+        // Copy the status and headers before consuming the response
+        let status = response.status();
+        let headers = response
+            .headers_names()
+            .into_iter()
+            .filter_map(|name| {
+                let value = response.header(&name)?.to_owned();
+                Some((name, value))
+            })
+            .collect::<Vec<_>>();
+
+        // Get response body
+        let body = response
+            .into_string()
+            .change_context(HttpError::FailedResponse)?;
+
+        let response = headers
+            .into_iter()
+            .fold(HttpResponse::new(status, body), |response, (name, value)| response.with_header(name, value));
+
+        Ok(response)
+    }
+}
@@ -8,9 +8,12 @@ pub use decision::Decision;
 
 // Regular modules
 pub mod client;
+pub mod config;
 pub mod conversion;
 pub mod datafile;
 pub mod decision;
+pub mod event_api;
+pub mod notification;
 
 #[cfg(feature = "online")]
-pub mod event_api;
+pub mod http;
@@ -0,0 +1,16 @@
+//! Notification listeners for decisions, conversions, events and datafile updates
+
+// Relative imports of sub modules
+pub use center::{ListenerId, NotificationCenter};
+pub use config_update::ConfigUpdateNotification;
+pub use decide::DecideNotification;
+#[cfg(feature = "online")]
+pub use log_event::LogEventNotification;
+pub use track::TrackNotification;
+
+mod center;
+mod config_update;
+mod decide;
+#[cfg(feature = "online")]
+mod log_event;
+mod track;
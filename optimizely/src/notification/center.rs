@@ -0,0 +1,184 @@
+// External imports
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+// Imports from super
+#[cfg(feature = "online")]
+use super::LogEventNotification;
+use super::{ConfigUpdateNotification, DecideNotification, TrackNotification};
+
+/// Identifier of a registered listener, used to remove the listener again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+// Listeners are reference counted, so they can be called without holding the lock
+type ListenerList<T> = Vec<(ListenerId, Arc<T>)>;
+
+#[derive(Default)]
+struct Listeners {
+    decide: ListenerList<dyn Fn(&DecideNotification) + Send + Sync>,
+    track: ListenerList<dyn Fn(&TrackNotification) + Send + Sync>,
+    #[cfg(feature = "online")]
+    log_event: ListenerList<dyn Fn(&LogEventNotification) + Send + Sync>,
+    config_update: ListenerList<dyn Fn(&ConfigUpdateNotification) + Send + Sync>,
+}
+
+/// Registry of listeners that are notified about activity of the client
+///
+/// ```
+/// use optimizely::Client;
+///
+/// // Initialize Optimizely client using local datafile
+/// let file_path = "../datafiles/sandbox.json";
+/// let optimizely_client = Client::from_local_datafile(file_path)?
+///     .initialize();
+///
+/// // Forward every decision to a custom analytics pipeline
+/// let listener_id = optimizely_client
+///     .notification_center()
+///     .add_decide_listener(|notification| {
+///         println!("{} {}", notification.user_id, notification.decision.variation_key());
+///     });
+///
+/// // Stop listening
+/// optimizely_client.notification_center().remove_listener(listener_id);
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Default)]
+pub struct NotificationCenter {
+    listeners: RwLock<Listeners>,
+    next_id: AtomicU64,
+}
+
+impl NotificationCenter {
+    /// Add a listener that is notified for every decision
+    pub fn add_decide_listener<F>(&self, listener: F) -> ListenerId
+    where
+        F: Fn(&DecideNotification) + Send + Sync + 'static,
+    {
+        let id = self.next_id();
+        self.write(|listeners| listeners.decide.push((id, Arc::new(listener))));
+        id
+    }
+
+    /// Add a listener that is notified for every tracked conversion event
+    pub fn add_track_listener<F>(&self, listener: F) -> ListenerId
+    where
+        F: Fn(&TrackNotification) + Send + Sync + 'static,
+    {
+        let id = self.next_id();
+        self.write(|listeners| listeners.track.push((id, Arc::new(listener))));
+        id
+    }
+
+    /// Add a listener that is notified for every request to the Event API
+    #[cfg(feature = "online")]
+    pub fn add_log_event_listener<F>(&self, listener: F) -> ListenerId
+    where
+        F: Fn(&LogEventNotification) + Send + Sync + 'static,
+    {
+        let id = self.next_id();
+        self.write(|listeners| listeners.log_event.push((id, Arc::new(listener))));
+        id
+    }
+
+    /// Add a listener that is notified when the client received a datafile with a new revision
+    pub fn add_config_update_listener<F>(&self, listener: F) -> ListenerId
+    where
+        F: Fn(&ConfigUpdateNotification) + Send + Sync + 'static,
+    {
+        let id = self.next_id();
+        self.write(|listeners| listeners.config_update.push((id, Arc::new(listener))));
+        id
+    }
+
+    /// Remove a listener, returns whether the listener was registered
+    pub fn remove_listener(&self, id: ListenerId) -> bool {
+        self.write(|listeners| {
+            let count = listeners.count();
+            listeners
+                .decide
+                .retain(|(listener_id, _)| *listener_id != id);
+            listeners
+                .track
+                .retain(|(listener_id, _)| *listener_id != id);
+            #[cfg(feature = "online")]
+            listeners
+                .log_event
+                .retain(|(listener_id, _)| *listener_id != id);
+            listeners
+                .config_update
+                .retain(|(listener_id, _)| *listener_id != id);
+            listeners.count() != count
+        })
+    }
+
+    /// Remove all listeners
+    pub fn clear(&self) {
+        self.write(|listeners| *listeners = Listeners::default());
+    }
+
+    pub(crate) fn notify_decide(&self, notification: &DecideNotification) {
+        let listeners = self.read(|listeners| Listeners::clone_list(&listeners.decide));
+        listeners.iter().for_each(|listener| listener(notification));
+    }
+
+    pub(crate) fn notify_track(&self, notification: &TrackNotification) {
+        let listeners = self.read(|listeners| Listeners::clone_list(&listeners.track));
+        listeners.iter().for_each(|listener| listener(notification));
+    }
+
+    #[cfg(feature = "online")]
+    pub(crate) fn notify_log_event(&self, notification: &LogEventNotification) {
+        let listeners = self.read(|listeners| Listeners::clone_list(&listeners.log_event));
+        listeners.iter().for_each(|listener| listener(notification));
+    }
+
+    #[cfg(feature = "online")]
+    pub(crate) fn notify_config_update(&self, build_notification: impl FnOnce() -> ConfigUpdateNotification) {
+        let listeners = self.read(|listeners| Listeners::clone_list(&listeners.config_update));
+
+        // Comparing the datafiles is only worth it when someone is listening
+        if listeners.is_empty() {
+            return;
+        }
+        let notification = build_notification();
+        listeners
+            .iter()
+            .for_each(|listener| listener(&notification));
+    }
+
+    fn next_id(&self) -> ListenerId {
+        ListenerId(self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Listeners) -> T) -> T {
+        f(&self
+            .listeners
+            .read()
+            .unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Listeners) -> T) -> T {
+        f(&mut self
+            .listeners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl Listeners {
+    fn count(&self) -> usize {
+        let count = self.decide.len() + self.track.len() + self.config_update.len();
+        #[cfg(feature = "online")]
+        let count = count + self.log_event.len();
+        count
+    }
+
+    fn clone_list<T: ?Sized>(list: &[(ListenerId, Arc<T>)]) -> Vec<Arc<T>> {
+        list.iter()
+            .map(|(_, listener)| Arc::clone(listener))
+            .collect()
+    }
+}
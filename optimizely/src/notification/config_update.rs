@@ -0,0 +1,13 @@
+// Imports from crate
+use crate::datafile::DatafileDiff;
+
+/// Notification that is sent when the client received a datafile with a new revision
+#[derive(Debug)]
+pub struct ConfigUpdateNotification {
+    /// Revision of the previous datafile
+    pub previous_revision: u32,
+    /// Revision of the new datafile
+    pub revision: u32,
+    /// Changes between the previous and the new datafile
+    pub diff: DatafileDiff,
+}
@@ -0,0 +1,18 @@
+// Imports from crate
+use crate::client::UserAttributes;
+use crate::Decision;
+
+/// Notification that is sent for every decision
+#[derive(Debug)]
+pub struct DecideNotification<'a> {
+    /// Key of the flag that was decided
+    pub flag_key: &'a str,
+    /// Id of the user for which the flag was decided
+    pub user_id: &'a str,
+    /// Attributes of the user for which the flag was decided
+    pub attributes: &'a UserAttributes,
+    /// Resulting decision, including the reasons if requested
+    pub decision: &'a Decision,
+    /// Whether a decision event was sent to the event dispatcher
+    pub decision_event_dispatched: bool,
+}
@@ -0,0 +1,8 @@
+/// Notification that is sent for every request to the Event API
+#[derive(Debug)]
+pub struct LogEventNotification<'a> {
+    /// URL of the Event API endpoint
+    pub endpoint: &'a str,
+    /// Payload of the request as JSON document
+    pub body: &'a str,
+}
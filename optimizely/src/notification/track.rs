@@ -0,0 +1,21 @@
+// External imports
+use std::collections::HashMap;
+
+// Imports from crate
+use crate::client::UserAttributes;
+use crate::conversion::EventTags;
+
+/// Notification that is sent for every tracked conversion event
+#[derive(Debug)]
+pub struct TrackNotification<'a> {
+    /// Key of the event that was tracked
+    pub event_key: &'a str,
+    /// Id of the user for which the event was tracked
+    pub user_id: &'a str,
+    /// Attributes of the user for which the event was tracked
+    pub attributes: &'a UserAttributes,
+    /// Properties of the conversion event
+    pub properties: &'a HashMap<String, String>,
+    /// Tags of the conversion event
+    pub tags: &'a EventTags,
+}
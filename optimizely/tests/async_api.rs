@@ -0,0 +1,140 @@
+#![cfg(feature = "async")]
+
+// External imports
+use std::time::Duration;
+use tokio::runtime::{Handle, Runtime};
+
+// Imports from Optimizely crate
+use optimizely::client::{DatafileFetcher, TrackError};
+use optimizely::{event_api::AsyncEventDispatcher, Client};
+
+// Relative imports of sub modules
+use common::{wait_until_async, FakeHttpClient, ACCOUNT_ID, FILE_PATH, REVISION, SDK_KEY};
+mod common;
+
+#[tokio::test]
+async fn from_fetcher_async() {
+    let http_client = FakeHttpClient::new();
+
+    // Download the datafile without blocking the runtime
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+    let client = Client::from_fetcher_async(fetcher)
+        .await
+        .expect("fake HTTP client should work")
+        .initialize();
+
+    // Check properties on client
+    assert_eq!(client.datafile().account_id(), ACCOUNT_ID);
+    assert_eq!(client.datafile().revision(), REVISION);
+}
+
+#[tokio::test]
+async fn async_polling_swaps_datafile() {
+    let http_client = FakeHttpClient::new();
+
+    // Poll the fake CDN every few milliseconds in a task
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+    let client = Client::from_fetcher_async(fetcher)
+        .await
+        .expect("fake HTTP client should work")
+        .with_async_polling_interval(Duration::from_millis(10))
+        .initialize();
+
+    // Publish a new revision and wait for the poller
+    http_client.set_revision(REVISION + 1);
+    wait_until_async(|| client.datafile().revision() == REVISION + 1).await;
+
+    // No more requests are made after dropping the client
+    drop(client);
+    let count = http_client.requests().len();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(http_client.requests().len(), count);
+}
+
+#[tokio::test]
+async fn async_event_dispatcher() {
+    let http_client = FakeHttpClient::new();
+
+    // Use the async event dispatcher with a fake HTTP client
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_event_dispatcher(AsyncEventDispatcher::new(Handle::current()))
+        .with_http_client(http_client.clone())
+        .initialize();
+
+    // Make decisions that send out decision events
+    for user_id in ["user1", "user2", "user3"] {
+        let user_context = client.create_user_context(user_id);
+        let _decision = user_context.decide("buy_button");
+    }
+
+    // Dropping the client sends the remaining events in the background
+    drop(client);
+    wait_until_async(|| !http_client.requests().is_empty()).await;
+
+    let requests = http_client.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body.matches("visitor_id").count(), 3);
+}
+
+#[tokio::test]
+async fn async_event_dispatcher_with_full_queue() {
+    // The task of the dispatcher does not run before this test yields, so the queue is never emptied
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_event_dispatcher(AsyncEventDispatcher::with_capacity(Handle::current(), 1))
+        .with_http_client(FakeHttpClient::new())
+        .initialize();
+
+    // The first event fills the queue
+    let user_context = client.create_user_context("user123");
+    user_context
+        .try_track_event("purchase")
+        .expect("queue should have room");
+
+    // The second event is rejected
+    let report = user_context.try_track_event("purchase").unwrap_err();
+    assert_eq!(report.current_context(), &TrackError::DispatcherQueueFull);
+}
+
+#[tokio::test]
+async fn async_event_dispatcher_with_steady_events() {
+    let http_client = FakeHttpClient::new();
+
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_event_dispatcher(AsyncEventDispatcher::new(Handle::current()))
+        .with_http_client(http_client.clone())
+        .initialize();
+
+    // New events keep arriving faster than the flush interval
+    for index in 0..8 {
+        let user_id = format!("user{index}");
+        let user_context = client.create_user_context(&user_id);
+        let _decision = user_context.decide("buy_button");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    // The first batch is still sent a second after its first event
+    assert!(!http_client.requests().is_empty());
+}
+
+#[test]
+fn async_event_dispatcher_outside_runtime() {
+    let runtime = Runtime::new().expect("runtime should be created");
+    let http_client = FakeHttpClient::new();
+
+    // The dispatcher can be created outside of the runtime that runs its task
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_event_dispatcher(AsyncEventDispatcher::new(runtime.handle().clone()))
+        .with_http_client(http_client.clone())
+        .initialize();
+
+    let user_context = client.create_user_context("user123");
+    let _decision = user_context.decide("buy_button");
+
+    // Dropping the client sends the remaining events on the runtime
+    drop(client);
+    runtime.block_on(wait_until_async(|| !http_client.requests().is_empty()));
+}
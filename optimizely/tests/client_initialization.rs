@@ -1,8 +1,15 @@
+// External imports
+use std::io::Read;
+
 // Imports from Optimizely crate
-use optimizely::{client::ClientError, datafile::DatafileError, Client};
+use optimizely::{
+    client::ClientError,
+    datafile::{DatafileError, Region, SUPPORTED_VERSIONS},
+    Client,
+};
 
 // Relative imports of sub modules
-use common::{ACCOUNT_ID, FILE_PATH, REVISION};
+use common::{ACCOUNT_ID, FILE_PATH, GROUPS_FILE_PATH, REVISION};
 mod common;
 
 #[test]
@@ -33,6 +40,7 @@ fn with_missing_properties() {
     // Valid JSON, but missing properties
     let json = r#"
     {
+        "version": "4",
         "accountId": "21537940595"
     }"#;
 
@@ -46,11 +54,34 @@ fn with_missing_properties() {
         "Report did not include ClientError::InvalidDatafile"
     );
 
-    // Verify the datafile error type
+    // Verify the datafile error type and path
     let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
-    assert!(
-        matches!(datafile_error, DatafileError::InvalidJson),
-        "Report did not include DatafileError::KeyNotFound"
+    assert_eq!(
+        *datafile_error,
+        DatafileError::MissingField {
+            path: "projectId".into()
+        }
+    );
+}
+
+#[test]
+fn with_missing_nested_property() {
+    // Remove a required field of the first experiment
+    let mut value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(FILE_PATH).unwrap()).expect("datafile should be JSON");
+    value["experiments"][0]
+        .as_object_mut()
+        .unwrap()
+        .remove("layerId");
+
+    // The missing field is reported at its own path, using the same path style as every other error
+    let report = Client::from_string(&value.to_string()).err().unwrap();
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(
+        *datafile_error,
+        DatafileError::MissingField {
+            path: "experiments[0].layerId".into()
+        }
     );
 }
 
@@ -59,6 +90,7 @@ fn with_invalid_array_properties() {
     // Valid JSON, but rollouts, experiments, and featureFlags should be an array
     let json = r#"
     {
+        "version": "4",
         "accountId": "21537940595",
         "revision": "73",
         "rollouts": null,
@@ -68,7 +100,7 @@ fn with_invalid_array_properties() {
     }"#;
 
     // Get error report
-    let report = Client::from_string(json.into()).err().unwrap();
+    let report = Client::from_string(json).err().unwrap();
 
     // Verify the client error type
     let client_error = report.downcast_ref::<ClientError>().unwrap();
@@ -77,11 +109,159 @@ fn with_invalid_array_properties() {
         "Report did not include ClientError::InvalidDatafile"
     );
 
-    // Verify the datafile error type
+    // Verify the datafile error type and path
     let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(
+        *datafile_error,
+        DatafileError::InvalidType {
+            path: "rollouts".into()
+        }
+    );
+}
+
+#[test]
+fn with_unsupported_version() {
+    // A future datafile format with a different structure
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content.replacen(r#""version": "4""#, r#""version": "5""#, 1);
+
+    // Get error report
+    let report = Client::from_string(&content).err().unwrap();
+
+    // Verify the client error type
+    let client_error = report.downcast_ref::<ClientError>().unwrap();
     assert!(
-        matches!(datafile_error, DatafileError::InvalidJson),
-        "Report did not include DatafileError::InvalidType"
+        matches!(client_error, ClientError::InvalidDatafile),
+        "Report did not include ClientError::InvalidDatafile"
+    );
+
+    // Verify the datafile error type and version
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(*datafile_error, DatafileError::UnsupportedVersion { version: 5 });
+}
+
+#[test]
+fn with_unknown_fields() {
+    // Add fields that might be introduced in the future
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content.replacen('{', r#"{"holdouts": [], "someFutureFlag": true,"#, 1);
+
+    let client = Client::from_string(&content)
+        .expect("datafile with unknown fields should work")
+        .initialize();
+
+    // Check version property on datafile
+    assert_eq!(client.datafile().version(), 4);
+    assert!(SUPPORTED_VERSIONS.contains(&client.datafile().version()));
+}
+
+#[test]
+fn with_invalid_revision() {
+    // The revision should be a string containing a number
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content.replacen(r#""revision": "73""#, r#""revision": "latest""#, 1);
+
+    // Get error report
+    let report = Client::from_string(&content).err().unwrap();
+
+    // Verify the datafile error type and path
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(
+        *datafile_error,
+        DatafileError::InvalidType {
+            path: "revision".into()
+        }
+    );
+}
+
+#[test]
+fn with_dangling_rollout_id() {
+    // Point a feature flag to a rollout that does not exist
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content =
+        content.replacen(r#""rolloutId": "rollout-19334-21533480907""#, r#""rolloutId": "rollout-does-not-exist""#, 1);
+
+    // Get error report
+    let report = Client::from_string(&content).err().unwrap();
+
+    // Verify the client error type
+    let client_error = report.downcast_ref::<ClientError>().unwrap();
+    assert!(
+        matches!(client_error, ClientError::InvalidDatafile),
+        "Report did not include ClientError::InvalidDatafile"
+    );
+
+    // Verify the datafile error type, path and reference
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert!(
+        matches!(
+            datafile_error,
+            DatafileError::DanglingReference { path, reference }
+                if path.ends_with(".rolloutId") && reference == "rollout-does-not-exist"
+        ),
+        "Report did not include DatafileError::DanglingReference"
+    );
+}
+
+#[test]
+fn with_several_dangling_references() {
+    // Point two feature flags to rollouts that do not exist
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content
+        .replacen(r#""rolloutId": "rollout-19334-21533480907""#, r#""rolloutId": "rollout-does-not-exist""#, 1)
+        .replacen(r#""rolloutId": "rollout-29807-21533480907""#, r#""rolloutId": "rollout-does-not-exist""#, 1);
+
+    // The flags are checked in the order of the datafile, so the first flag is reported every time
+    for _ in 0..10 {
+        let report = Client::from_string(&content).err().unwrap();
+        let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+        assert_eq!(
+            *datafile_error,
+            DatafileError::DanglingReference {
+                path: "featureFlags[0].rolloutId".into(),
+                reference: "rollout-does-not-exist".into(),
+            }
+        );
+    }
+}
+
+#[test]
+fn with_dangling_group_allocation() {
+    // Allocate the traffic of a group to an experiment that is not in the group
+    let content = std::fs::read_to_string(GROUPS_FILE_PATH).expect("datafile with groups should be readable");
+    let content = content.replacen(r#""entityId": "9300000125242""#, r#""entityId": "experiment-does-not-exist""#, 1);
+
+    // Get error report
+    let report = Client::from_string(&content).err().unwrap();
+
+    // Verify the datafile error type, path and reference
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(
+        *datafile_error,
+        DatafileError::DanglingReference {
+            path: "groups[0].trafficAllocation".into(),
+            reference: "experiment-does-not-exist".into(),
+        }
+    );
+}
+
+#[test]
+fn with_duplicate_experiment_key() {
+    // Give the last experiment the same key as the first experiment
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content.replacen(r#""key": "header_text_experiment""#, r#""key": "buy_button_experiment""#, 1);
+
+    // Get error report
+    let report = Client::from_string(&content).err().unwrap();
+
+    // Verify the datafile error type, path and key
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(
+        *datafile_error,
+        DatafileError::DuplicateKey {
+            path: "experiments[3].key".into(),
+            key: "buy_button_experiment".into(),
+        }
     );
 }
 
@@ -112,3 +292,159 @@ fn with_fixed_datafile() {
     // Check revision property on client
     assert_eq!(client.datafile().revision(), REVISION);
 }
+
+#[test]
+fn with_reader_bytes_and_value() {
+    let content = std::fs::read(FILE_PATH).expect("local datafile should be readable");
+
+    // Any reader can be used, like a downloaded object
+    let client = Client::from_reader(content.as_slice())
+        .expect("reader should work")
+        .initialize();
+    assert_eq!(client.datafile().revision(), REVISION);
+
+    let client = Client::from_bytes(&content)
+        .expect("bytes should work")
+        .initialize();
+    assert_eq!(client.datafile().revision(), REVISION);
+
+    // An already parsed JSON document
+    let value: serde_json::Value = serde_json::from_slice(&content).expect("datafile should be JSON");
+    let client = Client::from_value(value)
+        .expect("JSON value should work")
+        .initialize();
+    assert_eq!(client.datafile().account_id(), ACCOUNT_ID);
+}
+
+#[test]
+fn with_reader() {
+    // The version is checked after parsing the content of a reader
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content.replacen(r#""version": "4""#, r#""version": "5""#, 1);
+    let report = Client::from_reader(content.as_bytes()).err().unwrap();
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(*datafile_error, DatafileError::UnsupportedVersion { version: 5 });
+
+    // A failure while reading is not reported as an invalid datafile
+    let reader = content.as_bytes()[..100].chain(FailingReader);
+    let report = Client::from_reader(reader).err().unwrap();
+    let client_error = report.downcast_ref::<ClientError>().unwrap();
+    assert!(
+        matches!(client_error, ClientError::FailedFileRead),
+        "Report did not include ClientError::FailedFileRead"
+    );
+}
+
+// Reader that fails, like a connection that is closed while downloading
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("connection reset"))
+    }
+}
+
+#[test]
+fn with_embedded_datafile() {
+    // Datafile that is embedded in the test binary
+    const DATAFILE: &str = include_str!("../../datafiles/sandbox.json");
+
+    let client = Client::from_string(DATAFILE)
+        .expect("embedded datafile should work")
+        .initialize();
+    assert_eq!(client.datafile().revision(), REVISION);
+}
+
+#[test]
+fn with_invalid_value() {
+    // The revision should be a string containing a number
+    let mut value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(FILE_PATH).unwrap()).expect("datafile should be JSON");
+    value["revision"] = serde_json::json!(73);
+
+    // Get error report
+    let report = Client::from_value(value).err().unwrap();
+
+    // Verify the datafile error type and path
+    let datafile_error = report.downcast_ref::<DatafileError>().unwrap();
+    assert_eq!(
+        *datafile_error,
+        DatafileError::InvalidType {
+            path: "revision".into()
+        }
+    );
+}
+
+#[test]
+fn with_default_region() {
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .initialize();
+
+    // The bundled datafile does not specify a region
+    assert_eq!(client.datafile().region(), Region::US);
+}
+
+#[test]
+fn with_eu_region() {
+    // Add the region to the bundled datafile
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content.replacen('{', r#"{"region": "EU","#, 1);
+
+    let client = Client::from_string(&content)
+        .expect("datafile with region should work")
+        .initialize();
+
+    // Check region property on datafile
+    assert_eq!(client.datafile().region(), Region::EU);
+
+    // Events should be sent to the EU endpoint
+    #[cfg(feature = "online")]
+    assert_eq!(client.event_endpoint(), "https://eu.logx.optimizely.com/v1/events");
+}
+
+#[test]
+fn with_unknown_region() {
+    // Add a region to the bundled datafile, which this SDK does not know yet
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let content = content.replacen('{', r#"{"region": "AP","#, 1);
+
+    let client = Client::from_string(&content)
+        .expect("datafile with unknown region should work")
+        .initialize();
+
+    // Unknown regions fall back to the default region
+    assert_eq!(client.datafile().region(), Region::US);
+}
+
+#[test]
+#[cfg(feature = "online")]
+fn with_event_endpoint() {
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .initialize();
+
+    // Events are sent to the US endpoint by default
+    assert_eq!(client.event_endpoint(), "https://logx.optimizely.com/v1/events");
+
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_event_endpoint("http://localhost:8080/v1/events")
+        .initialize();
+
+    // A custom endpoint overrides the region
+    assert_eq!(client.event_endpoint(), "http://localhost:8080/v1/events");
+}
+
+#[test]
+fn event_dispatcher_accessors() {
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .initialize();
+
+    // Only online builds have a built-in event dispatcher
+    assert_eq!(client.optional_event_dispatcher().is_some(), cfg!(feature = "online"));
+
+    #[cfg(feature = "online")]
+    let _event_dispatcher = client.event_dispatcher();
+}
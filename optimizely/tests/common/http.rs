@@ -0,0 +1,139 @@
+// External imports
+use error_stack::Result;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Imports from Optimizely crate
+use optimizely::http::{HttpClient, HttpError, HttpResponse};
+
+// Imports from super
+use super::{FILE_PATH, REVISION};
+
+// A request that was made to the FakeHttpClient
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+// List of requests wrapped in a thread-safe reference counted mutable memory location
+type RequestList = Arc<Mutex<Vec<Request>>>;
+
+// HTTP client that serves the bundled datafile and accepts all events without using the network
+#[derive(Clone)]
+pub struct FakeHttpClient {
+    requests: RequestList,
+    datafile: Arc<Mutex<String>>,
+    revision: Arc<Mutex<u32>>,
+    get_status: Arc<Mutex<u16>>,
+    post_status: u16,
+    etag: bool,
+    get_delay: Duration,
+}
+
+impl FakeHttpClient {
+    pub fn new() -> FakeHttpClient {
+        FakeHttpClient::with_get_status(200)
+    }
+
+    pub fn with_get_status(get_status: u16) -> FakeHttpClient {
+        let datafile = std::fs::read_to_string(FILE_PATH).unwrap();
+        FakeHttpClient {
+            requests: RequestList::default(),
+            datafile: Arc::new(Mutex::new(datafile)),
+            revision: Arc::new(Mutex::new(REVISION)),
+            get_status: Arc::new(Mutex::new(get_status)),
+            post_status: 204,
+            etag: true,
+            get_delay: Duration::ZERO,
+        }
+    }
+
+    // Do not add an ETag header to responses, so conditional requests are not possible
+    pub fn without_etag() -> FakeHttpClient {
+        FakeHttpClient {
+            etag: false,
+            ..FakeHttpClient::new()
+        }
+    }
+
+    // Reject all events with the given status
+    pub fn with_post_status(post_status: u16) -> FakeHttpClient {
+        FakeHttpClient {
+            post_status,
+            ..FakeHttpClient::new()
+        }
+    }
+
+    // Wait before responding to downloads of the datafile, like an unreachable network
+    pub fn with_get_delay(get_delay: Duration) -> FakeHttpClient {
+        FakeHttpClient {
+            get_delay,
+            ..FakeHttpClient::new()
+        }
+    }
+
+    // Serve the bundled datafile with a different revision from now on
+    pub fn set_revision(&self, revision: u32) {
+        let mut datafile = self.datafile.lock().unwrap();
+        let mut current = self.revision.lock().unwrap();
+        *datafile = datafile.replace(&format!(r#""revision": "{current}""#), &format!(r#""revision": "{revision}""#));
+        *current = revision;
+    }
+
+    // Respond to downloads of the datafile with the given status from now on
+    pub fn set_get_status(&self, get_status: u16) {
+        *self.get_status.lock().unwrap() = get_status;
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn record(&self, method: &'static str, url: &str, headers: &[(&str, &str)], body: &str) {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        let request = Request {
+            method,
+            url: url.into(),
+            headers,
+            body: body.into(),
+        };
+        self.requests.lock().unwrap().push(request);
+    }
+}
+
+impl HttpClient for FakeHttpClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<HttpResponse, HttpError> {
+        self.record("GET", url, headers, "");
+        std::thread::sleep(self.get_delay);
+        let get_status = *self.get_status.lock().unwrap();
+
+        // Use the revision as ETag
+        if !self.etag {
+            let body = self.datafile.lock().unwrap().clone();
+            return Ok(HttpResponse::new(get_status, body));
+        }
+        let etag = format!(r#""{}""#, self.revision.lock().unwrap());
+
+        // Respond with not modified if the ETag matches, unless the CDN is down
+        let if_none_match = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("If-None-Match"));
+        if matches!(if_none_match, Some((_, value)) if *value == etag) && get_status == 200 {
+            return Ok(HttpResponse::new(304, "").with_header("ETag".into(), etag));
+        }
+
+        let body = self.datafile.lock().unwrap().clone();
+        Ok(HttpResponse::new(get_status, body).with_header("ETag".into(), etag))
+    }
+
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<HttpResponse, HttpError> {
+        self.record("POST", url, headers, body);
+        Ok(HttpResponse::new(self.post_status, ""))
+    }
+}
@@ -2,11 +2,23 @@
 #![allow(dead_code)]
 
 // External imports
-use std::cell::RefCell;
-use std::rc::Rc;
+use error_stack::Result;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+// Relative imports of sub modules
+// Not every test uses the fake HTTP client
+#[cfg(feature = "online")]
+#[allow(unused_imports)]
+pub use http::{FakeHttpClient, Request};
+#[cfg(feature = "online")]
+mod http;
 
 // Imports from Optimizely crate
-use optimizely::{Client, client::UserContext, Conversion, Decision, event_api::EventDispatcher};
+use optimizely::event_api::{DispatchError, EventDispatcher};
+use optimizely::{client::UserContext, decision::DecideOptions, Client, Conversion, Decision};
 
 // This is the account ID of mark.biesheuvel@optimizely.com
 pub const ACCOUNT_ID: &str = "21537940595";
@@ -18,14 +30,17 @@ pub const SDK_KEY: &str = "KVpGWnzPGKvvQ8yeEWmJZ";
 // This is a bundled copy of the JSON file that can be downloaded with the SDK key
 pub const FILE_PATH: &str = "../datafiles/sandbox.json";
 
+// This is a copy of the bundled datafile with two experiments in a mutual exclusion group
+pub const GROUPS_FILE_PATH: &str = "../datafiles/groups.json";
+
 // This is the revision number of the bundled datafile
 pub const REVISION: u32 = 73;
 
-// List of conversions wrapped in a reference counted mutable memory location
-type ConversionList = Rc<RefCell<Vec<Conversion>>>;
+// List of conversions wrapped in a thread-safe reference counted mutable memory location
+type ConversionList = Arc<Mutex<Vec<Conversion>>>;
 
-// List of decisions wrapped in a reference counted mutable memory location
-type DecisionList = Rc<RefCell<Vec<Decision>>>;
+// List of decisions wrapped in a thread-safe reference counted mutable memory location
+type DecisionList = Arc<Mutex<Vec<Decision>>>;
 
 // Struct that holds the EventList and implement the EventDispatcher trait
 #[derive(Default)]
@@ -37,21 +52,23 @@ pub(super) struct EventStore {
 // Return a new reference counted point to the list
 impl EventStore {
     fn conversions(&self) -> ConversionList {
-        Rc::clone(&self.conversions)
+        Arc::clone(&self.conversions)
     }
 
     fn decisions(&self) -> DecisionList {
-        Rc::clone(&self.decisions)
+        Arc::clone(&self.decisions)
     }
 }
 
 // Implementing the EventDispatcher using the interior mutability pattern
 impl EventDispatcher for EventStore {
-    fn send_conversion_event(&self, _user_context: &UserContext, conversion: Conversion){
-        self.conversions.borrow_mut().push(conversion);
+    fn send_conversion_event(&self, _user_context: &UserContext, conversion: Conversion) -> Result<(), DispatchError> {
+        self.conversions.lock().unwrap().push(conversion);
+        Ok(())
     }
-    fn send_decision_event(&self, _user_context: &UserContext, decision: Decision) {
-        self.decisions.borrow_mut().push(decision);
+    fn send_decision_event(&self, _user_context: &UserContext, decision: Decision) -> Result<(), DispatchError> {
+        self.decisions.lock().unwrap().push(decision);
+        Ok(())
     }
 }
 
@@ -66,6 +83,11 @@ pub struct TestContext {
 
 // A setup function used in multiple tests
 pub(super) fn setup() -> TestContext {
+    setup_with_default_decide_options(DecideOptions::default())
+}
+
+// A setup function for a client with default decide options
+pub(super) fn setup_with_default_decide_options(default_decide_options: DecideOptions) -> TestContext {
     // Create a struct to store events
     let event_store = EventStore::default();
 
@@ -77,7 +99,58 @@ pub(super) fn setup() -> TestContext {
     let client = Client::from_local_datafile(FILE_PATH)
         .expect("local datafile should work")
         .with_event_dispatcher(event_store)
+        .with_default_decide_options(default_decide_options)
         .initialize();
 
-    TestContext { client, conversions, decisions }
+    TestContext {
+        client,
+        conversions,
+        decisions,
+    }
+}
+
+// Maximum time to wait for a background thread or task, which is only reached when a test fails
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Interval at which the condition of a wait is checked
+const WAIT_INTERVAL: Duration = Duration::from_millis(5);
+
+// Wait until the condition holds, like a poller that has made a request, failing the test after a deadline
+pub fn wait_until(mut condition: impl FnMut() -> bool) {
+    let deadline = Instant::now() + WAIT_TIMEOUT;
+    while !condition() {
+        assert!(Instant::now() < deadline, "condition did not hold within {WAIT_TIMEOUT:?}");
+        sleep(WAIT_INTERVAL);
+    }
+}
+
+// Wait until the condition holds without blocking the runtime, failing the test after a deadline
+#[cfg(feature = "async")]
+pub async fn wait_until_async(mut condition: impl FnMut() -> bool) {
+    let deadline = Instant::now() + WAIT_TIMEOUT;
+    while !condition() {
+        assert!(Instant::now() < deadline, "condition did not hold within {WAIT_TIMEOUT:?}");
+        tokio::time::sleep(WAIT_INTERVAL).await;
+    }
+}
+
+// Empty directory that is unique for each test and removed at the end of the test
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("optimizely-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
 }
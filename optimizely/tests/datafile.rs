@@ -0,0 +1,96 @@
+// Imports from Optimizely crate
+use optimizely::datafile::Datafile;
+
+// Relative imports of sub modules
+use common::{setup, FILE_PATH, GROUPS_FILE_PATH, REVISION};
+mod common;
+
+#[test]
+fn experiment_by_key() {
+    let ctx = setup();
+    let datafile = ctx.client.datafile();
+
+    // A/B test can be found by key and by ID
+    let experiment = datafile.experiment_by_key("buy_button_experiment").unwrap();
+    assert_eq!(experiment.id(), "9300000127039");
+    assert_eq!(datafile.experiment(experiment.id()).unwrap().key(), "buy_button_experiment");
+
+    // Rules of a rollout can be found by key and by ID as well
+    let rule = datafile
+        .experiment_by_key("qa_rollout_targeted_delivery")
+        .unwrap();
+    assert_eq!(rule.id(), "9300000131788");
+    assert_eq!(datafile.experiment("9300000131788").unwrap().key(), "qa_rollout_targeted_delivery");
+
+    // Unknown keys are not found
+    assert!(datafile
+        .experiment_by_key("this_experiment_does_not_exist")
+        .is_none());
+}
+
+#[test]
+fn experiment_in_group() {
+    let content = std::fs::read_to_string(GROUPS_FILE_PATH).expect("datafile with groups should be readable");
+    let datafile = Datafile::build(&content).expect("datafile with groups should be valid");
+
+    // Experiments in a group are not evaluated, so they are not found by key or by ID
+    assert!(datafile
+        .experiment_by_key("hero_layout_experiment")
+        .is_none());
+    assert!(datafile.experiment("9300000090374").is_none());
+
+    // The flags that refer to them are still valid
+    assert!(datafile.flag("hero_layout").is_some());
+}
+
+#[test]
+fn variation_by_key() {
+    let ctx = setup();
+    let datafile = ctx.client.datafile();
+
+    // Variations are indexed per experiment
+    let experiment = datafile.experiment_by_key("buy_button_experiment").unwrap();
+    assert_eq!(experiment.variation_by_key("primary").unwrap().id(), "87755");
+    assert_eq!(experiment.variation_by_key("danger").unwrap().id(), "87756");
+    assert!(experiment.variation_by_key("off").is_none());
+}
+
+#[test]
+fn attribute_and_audience_lookups() {
+    let ctx = setup();
+    let datafile = ctx.client.datafile();
+
+    // Attribute key to ID
+    assert_eq!(datafile.attribute_id("is_employee"), Some("21870951122"));
+    assert_eq!(datafile.attribute_id("this_attribute_does_not_exist"), None);
+
+    // Audience ID to audience
+    let audience = datafile.audience("$opt_dummy_audience").unwrap();
+    assert_eq!(audience.name(), "Optimizely-Generated Audience for Backwards Compatibility");
+    assert!(datafile.audience("this_audience_does_not_exist").is_none());
+}
+
+#[test]
+fn serialize_round_trip() {
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let datafile = Datafile::build(&content).expect("local datafile should be valid");
+
+    // Serialize and build a copy of the datafile
+    let json = serde_json::to_string(&datafile).expect("datafile should serialize");
+    let copy = Datafile::build(&json).expect("serialized datafile should be valid");
+
+    // The copy contains the same entities
+    assert_eq!(copy.version(), 4);
+    assert_eq!(copy.revision(), REVISION);
+    assert_eq!(copy.account_id(), datafile.account_id());
+    assert_eq!(copy.project_id(), datafile.project_id());
+    let experiment = copy.experiment_by_key("buy_button_experiment").unwrap();
+    assert_eq!(experiment.variation_by_key("primary").unwrap().id(), "87755");
+    assert_eq!(copy.attribute_id("is_employee"), Some("21870951122"));
+    assert!(copy.event("purchase").is_some());
+    assert!(copy.audience("$opt_dummy_audience").is_some());
+
+    // Serializing the copy gives exactly the same output
+    let json_copy = serde_json::to_string(&copy).expect("datafile should serialize");
+    assert_eq!(json, json_copy);
+}
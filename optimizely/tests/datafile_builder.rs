@@ -0,0 +1,155 @@
+#![cfg(feature = "test-util")]
+
+// External imports
+use serde_json::json;
+
+// Imports from Optimizely crate
+use optimizely::{
+    datafile::{Datafile, DatafileBuilder, ExperimentBuilder, FlagBuilder, Region, VariationBuilder},
+    Client,
+};
+
+// Flag with an A/B test that includes half of the users, and a rollout for everyone else
+fn banner_flag() -> FlagBuilder {
+    let experiment = ExperimentBuilder::new("banner_experiment", "200")
+        .with_variation(VariationBuilder::new("off", "2000").with_feature_enabled(false))
+        .with_variation(VariationBuilder::new("green", "2001").with_variable_value("500", "#00FF00"))
+        .with_traffic_allocation("2000", 2_500)
+        .with_traffic_allocation("2001", 5_000);
+
+    let everyone_else = ExperimentBuilder::new("everyone_else", "201")
+        .with_variation(VariationBuilder::new("on", "2002"))
+        .with_traffic_allocation("2002", 10_000);
+
+    FlagBuilder::new("banner", "100")
+        .with_variable("color", "500", "string", "#000000")
+        .with_variable("style", "501", "json", "{}")
+        .with_experiment(experiment)
+        .with_rollout_rule(everyone_else)
+}
+
+#[test]
+fn build_datafile() {
+    let datafile = DatafileBuilder::new()
+        .with_revision(42)
+        .with_region(Region::EU)
+        .with_event("purchase", "300")
+        .with_attribute("country", "400")
+        .with_audience("600", "Dutch", json!(["and", {"name": "country", "value": "nl"}]))
+        .with_flag(banner_flag())
+        .build()
+        .expect("builder should create a valid datafile");
+
+    assert_eq!(datafile.revision(), 42);
+    assert_eq!(datafile.region(), Region::EU);
+    assert_eq!(datafile.event("purchase").unwrap().id(), "300");
+    assert_eq!(datafile.attribute_id("country"), Some("400"));
+    assert_eq!(datafile.audience("600").unwrap().name(), "Dutch");
+
+    // Experiments and rollout rules can be found by key
+    assert_eq!(
+        datafile
+            .experiment_by_key("banner_experiment")
+            .unwrap()
+            .id(),
+        "200"
+    );
+    assert_eq!(datafile.experiment_by_key("everyone_else").unwrap().id(), "201");
+}
+
+#[test]
+fn build_json() {
+    let builder = DatafileBuilder::new().with_flag(banner_flag());
+
+    // The JSON string is a valid datafile as well
+    let datafile = Datafile::build(&builder.to_json()).expect("builder should create valid JSON");
+    assert!(datafile.flag("banner").is_some());
+}
+
+#[test]
+fn decide_with_built_datafile() {
+    let json = DatafileBuilder::new().with_flag(banner_flag()).to_json();
+    let client = Client::from_string(&json)
+        .expect("datafile should be valid")
+        .initialize();
+
+    // Bucketing is deterministic, so every user gets the same variation of the experiment or the rollout each time
+    let expected = [
+        ("user0", "on"),
+        ("user1", "green"),
+        ("user2", "off"),
+        ("user3", "on"),
+        ("user4", "green"),
+        ("user5", "off"),
+        ("user6", "on"),
+        ("user7", "on"),
+    ];
+    for (user_id, variation_key) in expected {
+        let decision = client.create_user_context(user_id).decide("banner");
+        assert_eq!(decision.variation_key(), variation_key, "Unexpected variation for {user_id}");
+        assert_eq!(decision.enabled(), variation_key != "off");
+    }
+
+    // Variables are overridden by the variation
+    let config = client.optimizely_config();
+    let experiment = &config.flag("banner").unwrap().experiment_rules()[0];
+    let variation = experiment.variation("green").unwrap();
+    assert_eq!(variation.variables()[0].value(), "#00FF00");
+    assert_eq!(variation.variables()[1].variable_type(), "json");
+}
+
+#[test]
+fn diff_between_revisions() {
+    let previous = DatafileBuilder::new()
+        .with_revision(1)
+        .with_event("purchase", "300")
+        .with_audience("600", "Dutch", json!(["and", {"name": "country", "value": "nl"}]))
+        .with_flag(banner_flag());
+
+    // Identical datafiles have no changes
+    assert!(previous
+        .build()
+        .unwrap()
+        .diff(&previous.build().unwrap())
+        .is_empty());
+
+    // Publish a new revision with more traffic, a new variation, a changed audience, a new flag and a new event
+    let experiment = ExperimentBuilder::new("banner_experiment", "200")
+        .with_variation(VariationBuilder::new("off", "2000").with_feature_enabled(false))
+        .with_variation(VariationBuilder::new("green", "2001"))
+        .with_variation(VariationBuilder::new("blue", "2003"))
+        .with_traffic_allocation("2000", 3_000)
+        .with_traffic_allocation("2001", 6_000)
+        .with_traffic_allocation("2003", 9_000);
+    let everyone_else = ExperimentBuilder::new("everyone_else", "201")
+        .with_variation(VariationBuilder::new("on", "2002"))
+        .with_traffic_allocation("2002", 10_000);
+    let datafile = DatafileBuilder::new()
+        .with_revision(2)
+        .with_event("purchase", "300")
+        .with_event("signup", "301")
+        .with_audience("600", "Dutch", json!(["or", {"name": "country", "value": "nl"}]))
+        .with_flag(
+            FlagBuilder::new("banner", "100")
+                .with_experiment(experiment)
+                .with_rollout_rule(everyone_else),
+        )
+        .with_flag(FlagBuilder::new("checkout", "101"));
+
+    let diff = previous.build().unwrap().diff(&datafile.build().unwrap());
+    assert!(!diff.is_empty());
+    assert_eq!(diff.previous_revision(), 1);
+    assert_eq!(diff.revision(), 2);
+    assert_eq!(diff.flags_added(), ["checkout"]);
+    assert!(diff.flags_removed().is_empty());
+    assert_eq!(diff.traffic_allocations_changed(), ["banner_experiment"]);
+    assert_eq!(diff.variations_added(), [("banner_experiment".into(), "blue".into())]);
+    assert_eq!(diff.audiences_modified(), ["600"]);
+    assert_eq!(diff.events_added(), ["signup"]);
+
+    // The reverse diff contains the removals
+    let diff = datafile.build().unwrap().diff(&previous.build().unwrap());
+    assert_eq!(diff.flags_removed(), ["checkout"]);
+    assert_eq!(diff.events_removed(), ["signup"]);
+    assert!(diff.variations_added().is_empty());
+}
@@ -0,0 +1,232 @@
+#![cfg(feature = "online")]
+
+// External imports
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+// Imports from Optimizely crate
+use optimizely::{client::DatafileFetcher, Client};
+
+// Relative imports of sub modules
+use common::{wait_until, FakeHttpClient, TempDir, REVISION, SDK_KEY};
+mod common;
+
+#[test]
+fn cache_is_written_and_used_offline() {
+    let cache_dir = TempDir::new("cache-offline");
+
+    // Download the datafile, which is stored in the cache
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    let cache_path = fetcher.cache_path().unwrap().to_owned();
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+    assert!(cache_path.exists());
+
+    // Fall back to the cache while the CDN is down
+    let http_client = FakeHttpClient::with_get_status(500);
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(http_client.clone())
+        .with_cache_dir(cache_dir.path());
+    let client = Client::from_fetcher(fetcher)
+        .expect("cached datafile should work")
+        .initialize();
+
+    // The download was tried first
+    assert_eq!(client.datafile().revision(), REVISION);
+    assert_eq!(http_client.requests().len(), 1);
+
+    // Without a cache the client can not start
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(FakeHttpClient::with_get_status(500));
+    assert!(Client::from_fetcher(fetcher).is_err());
+}
+
+#[test]
+fn cache_is_used_when_download_is_slow() {
+    let cache_dir = TempDir::new("cache-slow");
+
+    // Fill the cache
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+
+    // The CDN does not respond before the startup timeout, so the client starts from the cache
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::with_get_delay(Duration::from_secs(60)))
+        .with_cache_dir(cache_dir.path())
+        .with_startup_timeout(Duration::from_millis(50));
+    let start = Instant::now();
+    let client = Client::from_fetcher(fetcher)
+        .expect("cached datafile should work")
+        .initialize();
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(client.datafile().revision(), REVISION);
+}
+
+#[test]
+fn cache_is_not_used_online() {
+    let cache_dir = TempDir::new("cache-online");
+
+    // Fill the cache
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+
+    // A new revision is downloaded, even without polling
+    let http_client = FakeHttpClient::new();
+    http_client.set_revision(REVISION + 1);
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(http_client)
+        .with_cache_dir(cache_dir.path());
+    let client = Client::from_fetcher(fetcher)
+        .expect("fake HTTP client should work")
+        .initialize();
+    assert_eq!(client.datafile().revision(), REVISION + 1);
+
+    // The cache is refreshed as well
+    let mut fetcher = DatafileFetcher::new(SDK_KEY).with_cache_dir(cache_dir.path());
+    assert_eq!(fetcher.load_cached().unwrap().revision(), REVISION + 1);
+}
+
+#[test]
+fn expired_cache_is_ignored() {
+    let cache_dir = TempDir::new("cache-expired");
+
+    // Fill the cache and pretend it was stored a long time ago
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    let cache_path = fetcher.cache_path().unwrap().to_owned();
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+    let mut entry: Value = serde_json::from_slice(&std::fs::read(&cache_path).unwrap()).unwrap();
+    entry["storedAt"] = 0.into();
+    std::fs::write(&cache_path, entry.to_string()).unwrap();
+
+    // The cache is older than a week, so the client can not start while the CDN is down
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::with_get_status(500))
+        .with_cache_dir(cache_dir.path());
+    assert!(Client::from_fetcher(fetcher).is_err());
+
+    // A longer maximum age accepts the cache
+    let mut fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_cache_dir(cache_dir.path())
+        .with_cache_max_age(Duration::MAX);
+    assert_eq!(fetcher.load_cached().unwrap().revision(), REVISION);
+}
+
+#[test]
+fn cache_is_refreshed_by_polling() {
+    let cache_dir = TempDir::new("cache-polling");
+
+    // Fill the cache
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+
+    // Start from the cache while the CDN is down and poll the fake CDN every few milliseconds
+    let http_client = FakeHttpClient::with_get_status(500);
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(http_client.clone())
+        .with_cache_dir(cache_dir.path());
+    let client = Client::from_fetcher(fetcher)
+        .expect("cached datafile should work")
+        .with_polling_interval(Duration::from_millis(10))
+        .initialize();
+
+    // Polling uses the ETag of the cached datafile
+    wait_until(|| http_client.requests().len() > 1);
+    let requests = http_client.requests();
+    let etag = requests[1]
+        .headers
+        .iter()
+        .find(|(name, _)| name == "If-None-Match");
+    let expected = format!(r#""{REVISION}""#);
+    assert_eq!(etag.map(|(_, value)| value), Some(&expected));
+
+    // The CDN recovers with a new revision, which is used and stored in the cache
+    http_client.set_revision(REVISION + 1);
+    http_client.set_get_status(200);
+    wait_until(|| client.datafile().revision() == REVISION + 1);
+    drop(client);
+
+    let mut fetcher = DatafileFetcher::new(SDK_KEY).with_cache_dir(cache_dir.path());
+    assert_eq!(fetcher.load_cached().unwrap().revision(), REVISION + 1);
+}
+
+#[test]
+fn corrupt_cache_is_ignored() {
+    let cache_dir = TempDir::new("cache-corrupt");
+
+    // Fill the cache and corrupt the datafile inside it, without breaking the JSON
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    let cache_path = fetcher.cache_path().unwrap().to_owned();
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+    let content = std::fs::read_to_string(&cache_path).unwrap();
+    std::fs::write(&cache_path, content.replacen("buy_button", "buy_buttom", 1)).unwrap();
+
+    // The checksum does not match, so the cache is ignored
+    let mut fetcher = DatafileFetcher::new(SDK_KEY).with_cache_dir(cache_dir.path());
+    assert!(fetcher.load_cached().is_none());
+
+    // A truncated cache file is ignored as well
+    std::fs::write(&cache_path, &content[..content.len() / 2]).unwrap();
+    assert!(fetcher.load_cached().is_none());
+
+    // The client downloads the datafile instead and replaces the cache file
+    let http_client = FakeHttpClient::new();
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(http_client.clone())
+        .with_cache_dir(cache_dir.path());
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+    assert_eq!(http_client.requests().len(), 1);
+
+    let mut fetcher = DatafileFetcher::new(SDK_KEY).with_cache_dir(cache_dir.path());
+    assert_eq!(fetcher.load_cached().unwrap().revision(), REVISION);
+}
+
+#[test]
+fn cache_file_stays_in_cache_dir() {
+    let cache_dir = TempDir::new("cache-escape");
+
+    // Path separators and dots in the SDK key are escaped
+    let fetcher = DatafileFetcher::new("../outside/key.json").with_cache_dir(cache_dir.path());
+    let cache_path = fetcher.cache_path().unwrap();
+    assert_eq!(cache_path.parent(), Some(cache_dir.path()));
+    assert_eq!(cache_path.file_name().unwrap(), "%2E%2E%2Foutside%2Fkey%2Ejson.json");
+
+    // Regular SDK keys are used as they are
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_cache_dir(cache_dir.path());
+    assert_eq!(fetcher.cache_path(), Some(cache_dir.path().join(format!("{SDK_KEY}.json")).as_path()));
+}
+
+#[test]
+fn concurrent_writers_do_not_corrupt_cache() {
+    let cache_dir = TempDir::new("cache-concurrent");
+
+    // Several clients store the same datafile in the same cache directory at the same time
+    let threads = (0..8)
+        .map(|_| {
+            let cache_dir = cache_dir.path().to_owned();
+            std::thread::spawn(move || {
+                let fetcher = DatafileFetcher::new(SDK_KEY)
+                    .with_http_client(FakeHttpClient::new())
+                    .with_cache_dir(cache_dir);
+                Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // The cache file is complete and no temporary files are left behind
+    let mut fetcher = DatafileFetcher::new(SDK_KEY).with_cache_dir(cache_dir.path());
+    assert_eq!(fetcher.load_cached().unwrap().revision(), REVISION);
+    assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 1);
+}
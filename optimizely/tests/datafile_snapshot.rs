@@ -0,0 +1,142 @@
+#![cfg(feature = "snapshot")]
+
+// Imports from Optimizely crate
+use optimizely::{
+    datafile::{Datafile, DatafileError, SNAPSHOT_FORMAT_VERSION},
+    decision::DecideOptions,
+    Client,
+};
+
+// Relative imports of sub modules
+use common::{FILE_PATH, GROUPS_FILE_PATH, REVISION};
+mod common;
+
+fn snapshot() -> Vec<u8> {
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let datafile = Datafile::build(&content).expect("local datafile should be valid");
+    datafile
+        .to_snapshot()
+        .expect("datafile should be written as snapshot")
+}
+
+#[test]
+fn snapshot_round_trip() {
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+    let snapshot = snapshot();
+
+    // The snapshot is smaller than the JSON datafile
+    assert!(snapshot.len() < content.len());
+
+    // Both clients make the same decisions, without sending decision events
+    let decide_options = DecideOptions {
+        disable_decision_event: true,
+        ..DecideOptions::default()
+    };
+    let json_client = Client::from_string(&content)
+        .unwrap()
+        .with_default_decide_options(decide_options.clone())
+        .initialize();
+    let snapshot_client = Client::from_snapshot(&snapshot)
+        .expect("snapshot should work")
+        .with_default_decide_options(decide_options)
+        .initialize();
+    assert_eq!(snapshot_client.datafile().revision(), REVISION);
+
+    for user_id in (0..100).map(|i| format!("user{i}")) {
+        for flag_key in ["buy_button", "qa_rollout"] {
+            let expected = json_client.create_user_context(&user_id).decide(flag_key);
+            let decision = snapshot_client
+                .create_user_context(&user_id)
+                .decide(flag_key);
+            assert_eq!(decision.variation_key(), expected.variation_key());
+        }
+    }
+
+    // Serializing gives the same JSON
+    let json = serde_json::to_string(&*json_client.datafile()).unwrap();
+    let json_copy = serde_json::to_string(&*snapshot_client.datafile()).unwrap();
+    assert_eq!(json, json_copy);
+}
+
+#[test]
+fn snapshot_keeps_indices() {
+    let content = std::fs::read_to_string(GROUPS_FILE_PATH).expect("datafile with groups should be readable");
+    let datafile = Datafile::build(&content).expect("datafile with groups should be valid");
+    let snapshot = datafile
+        .to_snapshot()
+        .expect("datafile should be written as snapshot");
+
+    // The lookup indices are loaded from the snapshot instead of rebuilt
+    let copy = Datafile::from_snapshot(&snapshot).expect("snapshot should work");
+    let experiment = copy
+        .experiment_by_key("sorting_algorithm_experiment")
+        .unwrap();
+    assert_eq!(experiment.id(), "9300000061857");
+    assert_eq!(
+        copy.experiment_by_key("buy_button_experiment")
+            .and_then(|experiment| experiment.variation_by_key("primary"))
+            .map(|variation| variation.id()),
+        Some("87755")
+    );
+
+    // The groups are kept, so both clients give the same decisions
+    let decide_options = DecideOptions {
+        disable_decision_event: true,
+        ..DecideOptions::default()
+    };
+    let json_client = Client::from_string(&content)
+        .unwrap()
+        .with_default_decide_options(decide_options.clone())
+        .initialize();
+    let snapshot_client = Client::from_snapshot(&snapshot)
+        .expect("snapshot should work")
+        .with_default_decide_options(decide_options)
+        .initialize();
+
+    for user_id in (0..100).map(|i| format!("user{i}")) {
+        for flag_key in ["hero_layout", "header_text", "sorting_algorithm"] {
+            let expected = json_client.create_user_context(&user_id).decide(flag_key);
+            let decision = snapshot_client
+                .create_user_context(&user_id)
+                .decide(flag_key);
+            assert_eq!(decision.experiment_id(), expected.experiment_id());
+            assert_eq!(decision.variation_key(), expected.variation_key());
+        }
+    }
+}
+
+#[test]
+fn snapshot_with_corrupt_payload() {
+    let mut snapshot = snapshot();
+
+    // Flip a bit in the payload
+    let last = snapshot.len() - 1;
+    snapshot[last] ^= 1;
+
+    let report = Datafile::from_snapshot(&snapshot).err().unwrap();
+    assert_eq!(report.downcast_ref::<DatafileError>(), Some(&DatafileError::InvalidSnapshot));
+
+    // Truncated snapshots and JSON are rejected as well
+    let report = Datafile::from_snapshot(&snapshot[..10]).err().unwrap();
+    assert_eq!(report.downcast_ref::<DatafileError>(), Some(&DatafileError::InvalidSnapshot));
+
+    let content = std::fs::read(FILE_PATH).unwrap();
+    let report = Datafile::from_snapshot(&content).err().unwrap();
+    assert_eq!(report.downcast_ref::<DatafileError>(), Some(&DatafileError::InvalidSnapshot));
+}
+
+#[test]
+fn snapshot_with_other_format_version() {
+    let mut snapshot = snapshot();
+
+    // The format version follows the magic bytes
+    snapshot[8..12].copy_from_slice(&(SNAPSHOT_FORMAT_VERSION + 1).to_le_bytes());
+
+    let report = Datafile::from_snapshot(&snapshot).err().unwrap();
+    assert_eq!(
+        report.downcast_ref::<DatafileError>(),
+        Some(&DatafileError::UnsupportedSnapshotVersion {
+            version: SNAPSHOT_FORMAT_VERSION + 1
+        })
+    );
+}
@@ -0,0 +1,195 @@
+// External imports
+#[cfg(feature = "online")]
+use std::time::Duration;
+
+// Imports from Optimizely crate
+#[cfg(feature = "online")]
+use optimizely::client::DatafileFetcher;
+use optimizely::{
+    client::{ClientError, DatafileSource},
+    Client,
+};
+
+// Relative imports of sub modules
+#[cfg(feature = "online")]
+use common::{wait_until, FakeHttpClient, TempDir, SDK_KEY};
+use common::{FILE_PATH, REVISION};
+mod common;
+
+#[test]
+fn first_source_that_succeeds_wins() {
+    let content = std::fs::read_to_string(FILE_PATH).expect("local datafile should be readable");
+
+    // The local file does not exist, so the string is used
+    let client = Client::from_sources([
+        DatafileSource::LocalFile("../datafiles/does_not_exist.json".into()),
+        DatafileSource::String(content),
+        DatafileSource::String("not even JSON".into()),
+    ])
+    .expect("datafile string should work")
+    .initialize();
+
+    assert_eq!(client.datafile().revision(), REVISION);
+}
+
+#[test]
+fn embedded_and_value_sources() {
+    const DATAFILE: &str = include_str!("../../datafiles/sandbox.json");
+
+    // Values from a configuration system are used before the embedded datafile
+    let value = serde_json::from_str(DATAFILE).expect("datafile should be JSON");
+    let client = Client::from_sources([
+        DatafileSource::Value(value),
+        DatafileSource::Embedded(DATAFILE),
+    ])
+    .expect("JSON value should work")
+    .initialize();
+    assert_eq!(client.datafile().revision(), REVISION);
+
+    // The embedded datafile is used when the value is invalid
+    let client = Client::from_sources([
+        DatafileSource::Value(serde_json::Value::Null),
+        DatafileSource::Embedded(DATAFILE),
+    ])
+    .expect("embedded datafile should work")
+    .initialize();
+    assert_eq!(client.datafile().revision(), REVISION);
+}
+
+#[test]
+fn all_failures_are_reported() {
+    let report = Client::from_sources([
+        DatafileSource::LocalFile("../datafiles/does_not_exist.json".into()),
+        DatafileSource::String("not even JSON".into()),
+    ])
+    .err()
+    .unwrap();
+
+    // Verify the client error type
+    let client_error = report.downcast_ref::<ClientError>().unwrap();
+    assert!(
+        matches!(client_error, ClientError::AllSourcesFailed),
+        "Report did not include ClientError::AllSourcesFailed"
+    );
+
+    // Verify the error of each source
+    let errors = report
+        .frames()
+        .filter_map(|frame| frame.downcast_ref::<ClientError>())
+        .collect::<Vec<_>>();
+    assert!(errors.contains(&&ClientError::FailedFileOpen));
+    assert!(errors.contains(&&ClientError::InvalidDatafile));
+
+    // Without any sources there is no datafile
+    let report = Client::from_sources([]).err().unwrap();
+    assert_eq!(report.downcast_ref::<ClientError>(), Some(&ClientError::DatafileMissing));
+}
+
+#[test]
+#[cfg(feature = "online")]
+fn failed_fetcher_is_used_for_polling() {
+    let http_client = FakeHttpClient::with_get_status(500);
+
+    // The CDN is down, so the local file is used
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+    let client = Client::from_sources([
+        DatafileSource::Fetcher(fetcher),
+        DatafileSource::LocalFile(FILE_PATH.into()),
+    ])
+    .expect("local datafile should work")
+    .with_polling_interval(Duration::from_millis(10))
+    .initialize();
+    assert_eq!(client.datafile().revision(), REVISION);
+
+    // The fetcher keeps trying to download the datafile
+    wait_until(|| http_client.requests().len() > 1);
+}
+
+#[test]
+#[cfg(feature = "online")]
+fn cache_file_source() {
+    let cache_dir = TempDir::new("sources");
+
+    // Fill the cache
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    let cache_path = fetcher.cache_path().unwrap().to_owned();
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+
+    // The CDN is down, so the cache file is used
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(FakeHttpClient::with_get_status(500));
+    let client = Client::from_sources([
+        DatafileSource::Fetcher(fetcher),
+        DatafileSource::CacheFile(cache_path),
+        DatafileSource::String("not even JSON".into()),
+    ])
+    .expect("cache file should work")
+    .initialize();
+
+    assert_eq!(client.datafile().revision(), REVISION);
+}
+
+#[test]
+#[cfg(feature = "online")]
+fn failed_cache_file_is_reported() {
+    let cache_dir = TempDir::new("sources-failed");
+
+    // Fill the cache and corrupt the datafile inside it, without breaking the JSON
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    let cache_path = fetcher.cache_path().unwrap().to_owned();
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+    let content = std::fs::read_to_string(&cache_path).unwrap();
+    std::fs::write(&cache_path, content.replacen("buy_button", "buy_buttom", 1)).unwrap();
+
+    // The report explains why the cache file was rejected
+    let report = Client::from_sources([DatafileSource::CacheFile(cache_path.clone())])
+        .err()
+        .unwrap();
+    let errors = report
+        .frames()
+        .filter_map(|frame| frame.downcast_ref::<ClientError>())
+        .collect::<Vec<_>>();
+    assert!(errors.contains(&&ClientError::FailedFileRead));
+    assert!(format!("{report:?}").contains("Checksum of the cached datafile does not match"));
+
+    // A missing cache file is reported as well
+    std::fs::remove_file(&cache_path).unwrap();
+    let report = Client::from_sources([DatafileSource::CacheFile(cache_path.clone())])
+        .err()
+        .unwrap();
+    assert!(format!("{report:?}").contains(&format!("Cache file: {}", cache_path.display())));
+}
+
+#[test]
+#[cfg(feature = "online")]
+fn cache_file_with_max_age() {
+    let cache_dir = TempDir::new("sources-max-age");
+
+    // Fill the cache
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_http_client(FakeHttpClient::new())
+        .with_cache_dir(cache_dir.path());
+    let cache_path = fetcher.cache_path().unwrap().to_owned();
+    Client::from_fetcher(fetcher).expect("fake HTTP client should work");
+
+    // The cache file is younger than a minute
+    let client = Client::from_sources([DatafileSource::CacheFileWithMaxAge(
+        cache_path.clone(),
+        Duration::from_secs(60),
+    )])
+    .expect("recent cache file should work")
+    .initialize();
+    assert_eq!(client.datafile().revision(), REVISION);
+
+    // Any age is too old when the maximum age is zero
+    let report = Client::from_sources([DatafileSource::CacheFileWithMaxAge(
+        cache_path,
+        Duration::ZERO,
+    )])
+    .err()
+    .unwrap();
+    assert!(format!("{report:?}").contains("Cached datafile is "));
+}
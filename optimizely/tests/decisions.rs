@@ -1,5 +1,8 @@
+// Imports from Optimizely crate
+use optimizely::{decision::DecideOptions, Client};
+
 // Relative imports of sub modules
-use common::setup;
+use common::{setup, setup_with_default_decide_options, GROUPS_FILE_PATH};
 mod common;
 
 macro_rules! assert_decision {
@@ -40,7 +43,7 @@ fn qa_rollout_flag() {
     assert_decision!(ctx, flag_key, "user15", true, "on");
 
     // Since this key is a rollout, no events should be dispatched
-    assert_eq!(ctx.decisions.borrow().len(), 0);
+    assert_eq!(ctx.decisions.lock().unwrap().len(), 0);
 }
 
 #[test]
@@ -83,7 +86,7 @@ fn buy_button_flag() {
     assert_decision!(ctx, flag_key, "user31", true, "primary");
 
     // Each of those 32 users should dispatch an event
-    assert_eq!(ctx.decisions.borrow().len(), 32);
+    assert_eq!(ctx.decisions.lock().unwrap().len(), 32);
 }
 
 #[test]
@@ -98,5 +101,53 @@ fn invalid_flag() {
     assert_decision!(ctx, flag_key, "user4", false, "off");
 
     // Since this key does not exist, no events should be dispatched
-    assert_eq!(ctx.decisions.borrow().len(), 0);
+    assert_eq!(ctx.decisions.lock().unwrap().len(), 0);
+}
+
+#[test]
+fn default_decide_options() {
+    // Never send decision events and always include reasons
+    let ctx = setup_with_default_decide_options(DecideOptions {
+        disable_decision_event: true,
+        include_reasons: true,
+        ..DecideOptions::default()
+    });
+    let user_context = ctx.client.create_user_context("user0");
+
+    // Default options apply to decisions without options
+    let decision = user_context.decide("buy_button");
+    assert!(!decision.reasons().is_empty());
+
+    // Reasons refer to the experiment by key
+    assert!(decision.reasons()[0].ends_with(r#"of experiment "buy_button_experiment"."#));
+
+    // Default options are combined with the options of the call
+    let decide_options = DecideOptions {
+        exclude_variables: true,
+        ..DecideOptions::default()
+    };
+    let decision = user_context.decide_with_options("buy_button", &decide_options);
+    assert!(!decision.reasons().is_empty());
+
+    // No events should be dispatched
+    assert_eq!(ctx.decisions.lock().unwrap().len(), 0);
+}
+
+#[test]
+fn experiments_in_groups_are_skipped() {
+    // The experiments of the flags hero_layout and header_text are in a group, which is not supported yet
+    let client = Client::from_local_datafile(GROUPS_FILE_PATH)
+        .expect("datafile with groups should work")
+        .with_default_decide_options(DecideOptions {
+            disable_decision_event: true,
+            ..DecideOptions::default()
+        })
+        .initialize();
+
+    // Every user falls back to the rollout of the flag
+    for user_id in (0..100).map(|i| format!("user{i}")) {
+        let user_context = client.create_user_context(&user_id);
+        assert_eq!(user_context.decide("hero_layout").experiment_id(), "default-rollout-28662-21533480907");
+        assert_eq!(user_context.decide("header_text").experiment_id(), "default-rollout-19359-21533480907");
+    }
 }
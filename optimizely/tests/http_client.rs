@@ -0,0 +1,179 @@
+#![cfg(feature = "online")]
+
+// Imports from Optimizely crate
+use optimizely::client::{ClientError, DatafileFetcher, TrackError};
+use optimizely::{event_api::BatchedEventDispatcher, event_tags, Client};
+use serde_json::Value;
+
+// Relative imports of sub modules
+use common::{FakeHttpClient, ACCOUNT_ID, FILE_PATH, REVISION, SDK_KEY};
+mod common;
+
+#[test]
+fn fetcher_with_http_client() {
+    let http_client = FakeHttpClient::new();
+
+    // Download the datafile without using the network
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+    let client = Client::from_fetcher(fetcher)
+        .expect("fake HTTP client should work")
+        .initialize();
+
+    // Check properties on client
+    assert_eq!(client.datafile().account_id(), ACCOUNT_ID);
+    assert_eq!(client.datafile().revision(), REVISION);
+
+    // Exactly one request is made to the CDN
+    let requests = http_client.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].url, format!("https://cdn.optimizely.com/datafiles/{SDK_KEY}.json"));
+}
+
+#[test]
+fn fetcher_with_not_found() {
+    let http_client = FakeHttpClient::with_get_status(404);
+
+    // Get error report
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client);
+    let report = Client::from_fetcher(fetcher).err().unwrap();
+
+    // Verify the client error type
+    let client_error = report.downcast_ref::<ClientError>().unwrap();
+    assert!(
+        matches!(client_error, ClientError::FailedRequest),
+        "Report did not include ClientError::FailedRequest"
+    );
+}
+
+#[test]
+fn simple_event_dispatcher_with_http_client() {
+    let http_client = FakeHttpClient::new();
+
+    // Use the default event dispatcher with a fake HTTP client
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_http_client(http_client.clone())
+        .initialize();
+
+    // Make a decision that sends out a decision event
+    let user_context = client.create_user_context("user123");
+    let _decision = user_context.decide("buy_button");
+
+    // Exactly one request is made to the Event API
+    let requests = http_client.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].url, "https://logx.optimizely.com/v1/events");
+    assert!(requests[0]
+        .headers
+        .contains(&("content-type".into(), "application/json".into())));
+    assert!(requests[0].body.contains(ACCOUNT_ID));
+}
+
+#[test]
+fn simple_event_dispatcher_with_failed_request() {
+    let http_client = FakeHttpClient::with_post_status(500);
+
+    // Use the default event dispatcher with a fake HTTP client that rejects all events
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_http_client(http_client.clone())
+        .initialize();
+
+    // The failed request is reported
+    let user_context = client.create_user_context("user123");
+    let report = user_context.try_track_event("purchase").unwrap_err();
+    assert_eq!(report.current_context(), &TrackError::DispatcherSendFailed);
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[test]
+fn batched_event_dispatcher_with_http_client() {
+    let http_client = FakeHttpClient::new();
+
+    // Use the batched event dispatcher with a fake HTTP client
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_event_dispatcher(BatchedEventDispatcher::default())
+        .with_http_client(http_client.clone())
+        .initialize();
+
+    // Make decisions that send out decision events
+    for user_id in ["user1", "user2", "user3"] {
+        let user_context = client.create_user_context(user_id);
+        let _decision = user_context.decide("buy_button");
+    }
+
+    // Dropping the client sends the remaining events in a single batch
+    drop(client);
+
+    let requests = http_client.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body.matches("visitor_id").count(), 3);
+}
+
+#[test]
+fn fetcher_with_access_token() {
+    let http_client = FakeHttpClient::new();
+
+    // Download the datafile of a secure environment
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_access_token("secret-token")
+        .with_http_client(http_client.clone());
+    let _client = Client::from_fetcher(fetcher)
+        .expect("fake HTTP client should work")
+        .initialize();
+
+    // The authenticated endpoint is used with a bearer token
+    let requests = http_client.requests();
+    assert_eq!(requests[0].url, format!("https://config.optimizely.com/datafiles/auth/{SDK_KEY}.json"));
+    assert!(requests[0]
+        .headers
+        .contains(&("Authorization".into(), "Bearer secret-token".into())));
+}
+
+#[test]
+fn fetcher_with_url_template() {
+    // Without access token the public endpoint is used
+    let fetcher = DatafileFetcher::new(SDK_KEY);
+    assert_eq!(fetcher.url(), format!("https://cdn.optimizely.com/datafiles/{SDK_KEY}.json"));
+
+    // A custom URL template overrides the default endpoints
+    let fetcher = DatafileFetcher::new(SDK_KEY)
+        .with_access_token("secret-token")
+        .with_url_template("https://proxy.example.com/{sdk_key}/datafile.json");
+    assert_eq!(fetcher.url(), format!("https://proxy.example.com/{SDK_KEY}/datafile.json"));
+}
+
+#[test]
+fn conversion_with_revenue_and_value() {
+    let http_client = FakeHttpClient::new();
+
+    // Use the default event dispatcher with a fake HTTP client
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_http_client(http_client.clone())
+        .initialize();
+
+    // Send a conversion event with typed tags
+    let user_context = client.create_user_context("user123");
+    let tags = event_tags! {
+        "revenue" => 1299,
+        "value" => 4.5,
+        "category" => "shoes",
+    };
+    user_context.track_event_with_properties_and_tags("purchase", Default::default(), tags);
+
+    // Find the event within the payload
+    let requests = http_client.requests();
+    assert_eq!(requests.len(), 1);
+    let payload: Value = serde_json::from_str(&requests[0].body).unwrap();
+    let event = &payload["visitors"][0]["snapshots"][0]["events"][0];
+
+    // Revenue and value are top-level numeric fields, tags keep their types
+    assert_eq!(event["revenue"], 1299);
+    assert_eq!(event["value"], 4.5);
+    assert_eq!(event["tags"]["category"], "shoes");
+    assert_eq!(event["tags"]["revenue"], 1299);
+}
@@ -0,0 +1,193 @@
+// External imports
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "online")]
+use std::time::Duration;
+
+// Imports from Optimizely crate
+#[cfg(feature = "online")]
+use optimizely::{client::DatafileFetcher, Client};
+use optimizely::{decision::DecideOptions, event_tags};
+
+// Relative imports of sub modules
+use common::setup;
+#[cfg(feature = "online")]
+use common::{wait_until, FakeHttpClient, ACCOUNT_ID, FILE_PATH, REVISION, SDK_KEY};
+mod common;
+
+#[test]
+fn decide_listener() {
+    let ctx = setup();
+
+    // Collect the flag key, variation key and whether an event was dispatched
+    let notifications = Arc::new(Mutex::new(Vec::new()));
+    let list = Arc::clone(&notifications);
+    ctx.client
+        .notification_center()
+        .add_decide_listener(move |notification| {
+            let variation_key = notification.decision.variation_key().to_owned();
+            let entry = (notification.flag_key.to_owned(), variation_key, notification.decision_event_dispatched);
+            list.lock().unwrap().push(entry);
+        });
+
+    // Decide an A/B test, a rollout and a flag that does not exist
+    let user_context = ctx.client.create_user_context("user0");
+    user_context.decide("buy_button");
+    user_context.decide("qa_rollout");
+    user_context.decide("this_flag_does_not_exist");
+
+    // Only the A/B test dispatches a decision event
+    let notifications = notifications.lock().unwrap();
+    assert_eq!(notifications.len(), 3);
+    assert_eq!(notifications[0], ("buy_button".into(), "primary".into(), true));
+    assert_eq!(notifications[1], ("qa_rollout".into(), "off".into(), false));
+    assert_eq!(notifications[2], ("this_flag_does_not_exist".into(), "off".into(), false));
+}
+
+#[test]
+fn decide_listener_with_reasons() {
+    let ctx = setup();
+
+    // Collect the reasons of each decision
+    let reasons = Arc::new(Mutex::new(Vec::new()));
+    let list = Arc::clone(&reasons);
+    ctx.client
+        .notification_center()
+        .add_decide_listener(move |notification| {
+            list.lock()
+                .unwrap()
+                .extend(notification.decision.reasons().to_vec());
+        });
+
+    // Reasons are only collected when requested
+    let user_context = ctx.client.create_user_context("user0");
+    user_context.decide("buy_button");
+    assert!(reasons.lock().unwrap().is_empty());
+
+    let decide_options = DecideOptions {
+        include_reasons: true,
+        ..DecideOptions::default()
+    };
+    let decision = user_context.decide_with_options("buy_button", &decide_options);
+    assert_eq!(decision.reasons().len(), 1);
+    assert_eq!(*reasons.lock().unwrap(), decision.reasons());
+}
+
+#[test]
+fn remove_listener() {
+    let ctx = setup();
+
+    // Count the number of notifications
+    let count = Arc::new(Mutex::new(0));
+    let counter = Arc::clone(&count);
+    let listener_id = ctx
+        .client
+        .notification_center()
+        .add_decide_listener(move |_| {
+            *counter.lock().unwrap() += 1;
+        });
+
+    let user_context = ctx.client.create_user_context("user0");
+    user_context.decide("buy_button");
+
+    // Removing the listener only succeeds once
+    assert!(ctx
+        .client
+        .notification_center()
+        .remove_listener(listener_id));
+    assert!(!ctx
+        .client
+        .notification_center()
+        .remove_listener(listener_id));
+
+    // No more notifications after removing
+    user_context.decide("buy_button");
+    assert_eq!(*count.lock().unwrap(), 1);
+}
+
+#[test]
+fn track_listener() {
+    let ctx = setup();
+
+    // Collect event keys and tags
+    let notifications = Arc::new(Mutex::new(Vec::new()));
+    let list = Arc::clone(&notifications);
+    ctx.client
+        .notification_center()
+        .add_track_listener(move |notification| {
+            let entry = (notification.event_key.to_owned(), notification.tags.clone());
+            list.lock().unwrap().push(entry);
+        });
+
+    // Send a conversion event with tags
+    let user_context = ctx.client.create_user_context("user0");
+    let tags = event_tags! { "category" => "shoes" };
+    user_context.track_event_with_properties_and_tags("purchase", Default::default(), tags);
+
+    let notifications = notifications.lock().unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].0, "purchase");
+    assert_eq!(notifications[0].1.get("category").unwrap(), "shoes");
+}
+
+#[test]
+#[cfg(feature = "online")]
+fn log_event_listener() {
+    let http_client = FakeHttpClient::new();
+
+    // Use the default event dispatcher with a fake HTTP client
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_http_client(http_client)
+        .initialize();
+
+    // Collect the outgoing payloads
+    let bodies = Arc::new(Mutex::new(Vec::new()));
+    let list = Arc::clone(&bodies);
+    client
+        .notification_center()
+        .add_log_event_listener(move |notification| {
+            assert_eq!(notification.endpoint, "https://logx.optimizely.com/v1/events");
+            list.lock().unwrap().push(notification.body.to_owned());
+        });
+
+    // Make a decision that sends out a decision event
+    let user_context = client.create_user_context("user0");
+    user_context.decide("buy_button");
+
+    let bodies = bodies.lock().unwrap();
+    assert_eq!(bodies.len(), 1);
+    assert!(bodies[0].contains(ACCOUNT_ID));
+}
+
+#[test]
+#[cfg(feature = "online")]
+fn config_update_listener() {
+    let http_client = FakeHttpClient::new();
+
+    // Poll the fake CDN every few milliseconds
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+    let client = Client::from_fetcher(fetcher)
+        .expect("fake HTTP client should work")
+        .with_polling_interval(Duration::from_millis(10))
+        .initialize();
+
+    // Collect the revisions
+    let revisions = Arc::new(Mutex::new(Vec::new()));
+    let list = Arc::clone(&revisions);
+    client
+        .notification_center()
+        .add_config_update_listener(move |notification| {
+            // Only the revision changed, so the diff is empty
+            assert!(notification.diff.is_empty());
+            list.lock()
+                .unwrap()
+                .push((notification.previous_revision, notification.revision));
+        });
+
+    // Publish a new revision and wait for the poller
+    http_client.set_revision(REVISION + 1);
+    wait_until(|| !revisions.lock().unwrap().is_empty());
+
+    // Exactly one update, since the revision only changed once
+    assert_eq!(*revisions.lock().unwrap(), vec![(REVISION, REVISION + 1)]);
+}
@@ -0,0 +1,158 @@
+// Imports from Optimizely crate
+use optimizely::Client;
+
+// Relative imports of sub modules
+use common::{setup, ACCOUNT_ID, REVISION};
+mod common;
+
+#[test]
+fn config_from_datafile() {
+    let ctx = setup();
+    let config = ctx.client.optimizely_config();
+
+    // Check properties of the environment
+    assert_eq!(config.account_id(), ACCOUNT_ID);
+    assert_eq!(config.environment_key(), "development");
+    assert_eq!(config.revision(), REVISION);
+
+    // Flags are sorted by key
+    let flag_keys = config
+        .flags()
+        .iter()
+        .map(|flag| flag.key())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        flag_keys,
+        [
+            "buy_button",
+            "header_text",
+            "hero_layout",
+            "qa_rollout",
+            "simplified_checkout",
+            "sorting_algorithm"
+        ]
+    );
+
+    // Events and attributes are listed
+    let event_keys = config
+        .events()
+        .iter()
+        .map(|event| event.key())
+        .collect::<Vec<_>>();
+    assert_eq!(event_keys, ["purchase", "subscribe"]);
+    assert_eq!(config.attributes()[0].key(), "is_employee");
+
+    // Legacy audiences keep their conditions string
+    let audience = &config.audiences()[0];
+    assert_eq!(audience.id(), "$opt_dummy_audience");
+    assert!(audience.conditions().starts_with(r#"["or""#));
+}
+
+#[test]
+fn config_flag_rules() {
+    let ctx = setup();
+    let config = ctx.client.optimizely_config();
+
+    // A/B test with four variations and a single delivery rule
+    let flag = config.flag("buy_button").unwrap();
+    assert_eq!(flag.experiment_rules().len(), 1);
+    assert_eq!(flag.delivery_rules().len(), 1);
+    let experiment = &flag.experiment_rules()[0];
+    assert_eq!(experiment.key(), "buy_button_experiment");
+    let variation_keys = experiment
+        .variations()
+        .iter()
+        .map(|variation| variation.key())
+        .collect::<Vec<_>>();
+    assert_eq!(variation_keys, ["danger", "primary", "success", "warning"]);
+
+    // Targeted delivery followed by the "Everyone Else" rule
+    let flag = config.flag("qa_rollout").unwrap();
+    assert!(flag.experiment_rules().is_empty());
+    let rule_keys = flag
+        .delivery_rules()
+        .iter()
+        .map(|rule| rule.key())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rule_keys,
+        [
+            "qa_rollout_targeted_delivery",
+            "default-rollout-19334-21533480907"
+        ]
+    );
+
+    // Unknown flags are not listed
+    assert!(config.flag("this_flag_does_not_exist").is_none());
+}
+
+#[test]
+fn config_with_variables_and_typed_audiences() {
+    let json = r#"
+    {
+        "version": "4",
+        "accountId": "21537940595",
+        "projectId": "21537940595",
+        "environmentKey": "production",
+        "revision": "1",
+        "botFiltering": false,
+        "anonymizeIP": true,
+        "events": [],
+        "attributes": [{"id": "1", "key": "country"}],
+        "audiences": [{"id": "10", "name": "Dutch", "conditions": "[\"or\"]"}],
+        "typedAudiences": [
+            {"id": "10", "name": "Dutch", "conditions": ["and", {"name": "country", "value": "nl"}]}
+        ],
+        "experiments": [],
+        "rollouts": [{
+            "id": "rollout-1",
+            "experiments": [{
+                "id": "100",
+                "key": "everyone_else",
+                "layerId": "rollout-1",
+                "audienceIds": ["10"],
+                "trafficAllocation": [{"entityId": "1000", "endOfRange": 10000}],
+                "variations": [{
+                    "id": "1000",
+                    "key": "on",
+                    "featureEnabled": true,
+                    "variables": [{"id": "500", "value": "Welcome!"}]
+                }]
+            }]
+        }],
+        "featureFlags": [{
+            "id": "50",
+            "key": "banner",
+            "rolloutId": "rollout-1",
+            "experimentIds": [],
+            "variables": [
+                {"id": "500", "key": "title", "type": "string", "defaultValue": "Hello"},
+                {"id": "501", "key": "style", "type": "string", "subType": "json", "defaultValue": "{}"}
+            ]
+        }]
+    }"#;
+
+    let client = Client::from_string(json)
+        .expect("datafile should be valid")
+        .initialize();
+    let config = client.optimizely_config();
+
+    // Variables of the flag have their default values
+    let flag = config.flag("banner").unwrap();
+    assert_eq!(flag.variables()[0].value(), "Hello");
+    assert_eq!(flag.variables()[1].variable_type(), "json");
+
+    // Variables of the variation are overridden
+    let rule = &flag.delivery_rules()[0];
+    assert_eq!(rule.audience_ids(), ["10"]);
+
+    // Without audience conditions, the audience IDs are combined with "or"
+    assert_eq!(rule.audience_conditions(), r#"["or","10"]"#);
+    let variation = rule.variation("on").unwrap();
+    assert_eq!(variation.variables()[0].value(), "Welcome!");
+    assert_eq!(variation.variables()[1].value(), "{}");
+
+    // Typed audiences take precedence and their conditions are serialized
+    assert_eq!(config.audiences().len(), 1);
+    assert_eq!(config.audiences()[0].conditions(), r#"["and",{"name":"country","value":"nl"}]"#);
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "online")]
+
+// External imports
+use std::thread::sleep;
+use std::time::Duration;
+
+// Imports from Optimizely crate
+use optimizely::{client::DatafileFetcher, Client};
+
+// Relative imports of sub modules
+use common::{wait_until, FakeHttpClient, FILE_PATH, REVISION, SDK_KEY};
+mod common;
+
+#[test]
+fn polling_swaps_datafile() {
+    let http_client = FakeHttpClient::new();
+
+    // Poll the fake CDN every few milliseconds
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+    let client = Client::from_fetcher(fetcher)
+        .expect("fake HTTP client should work")
+        .with_polling_interval(Duration::from_millis(10))
+        .initialize();
+
+    // Create a user context before the datafile is updated
+    let user_context = client.create_user_context("user123");
+    assert_eq!(client.datafile().revision(), REVISION);
+
+    // Publish a new revision and wait for the poller
+    http_client.set_revision(REVISION + 1);
+    wait_until(|| client.datafile().revision() == REVISION + 1);
+
+    // The existing user context keeps using its snapshot
+    assert_eq!(user_context.datafile().revision(), REVISION);
+}
+
+#[test]
+fn polling_stops_on_drop() {
+    let http_client = FakeHttpClient::new();
+
+    // Poll the fake CDN every few milliseconds
+    let fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+    let client = Client::from_fetcher(fetcher)
+        .expect("fake HTTP client should work")
+        .with_polling_interval(Duration::from_millis(10))
+        .initialize();
+
+    // Initial request and at least one poll
+    wait_until(|| http_client.requests().len() > 1);
+
+    // No more requests are made after dropping the client
+    drop(client);
+    let count = http_client.requests().len();
+    sleep(Duration::from_millis(100));
+    assert_eq!(http_client.requests().len(), count);
+}
+
+#[test]
+fn polling_without_sdk_key() {
+    // Polling interval is ignored for a local datafile
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_polling_interval(Duration::from_millis(10))
+        .initialize();
+
+    assert_eq!(client.datafile().revision(), REVISION);
+}
+
+#[test]
+fn fetch_if_changed_with_etag() {
+    let http_client = FakeHttpClient::new();
+    let mut fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+
+    // The first request downloads the datafile and stores the ETag
+    let datafile = fetcher.fetch().expect("fake HTTP client should work");
+    assert_eq!(datafile.revision(), REVISION);
+    assert_eq!(fetcher.etag(), Some(r#""73""#));
+
+    // The conditional request is answered with not modified
+    let datafile = fetcher
+        .fetch_if_changed()
+        .expect("fake HTTP client should work");
+    assert!(datafile.is_none());
+    let requests = http_client.requests();
+    assert!(requests[1]
+        .headers
+        .contains(&("If-None-Match".into(), r#""73""#.into())));
+
+    // A new revision is downloaded again
+    http_client.set_revision(REVISION + 1);
+    let datafile = fetcher
+        .fetch_if_changed()
+        .expect("fake HTTP client should work");
+    assert_eq!(datafile.map(|datafile| datafile.revision()), Some(REVISION + 1));
+}
+
+#[test]
+fn fetch_if_changed_without_etag() {
+    let http_client = FakeHttpClient::without_etag();
+    let mut fetcher = DatafileFetcher::new(SDK_KEY).with_http_client(http_client.clone());
+
+    // The first request downloads the datafile
+    let datafile = fetcher.fetch().expect("fake HTTP client should work");
+    assert_eq!(datafile.revision(), REVISION);
+    assert_eq!(fetcher.etag(), None);
+
+    // Without ETag the datafile is downloaded, but not built when the revision is the same
+    let datafile = fetcher
+        .fetch_if_changed()
+        .expect("fake HTTP client should work");
+    assert!(datafile.is_none());
+    assert!(http_client.requests()[1].headers.is_empty());
+
+    // A new revision is built again
+    http_client.set_revision(REVISION + 1);
+    let datafile = fetcher
+        .fetch_if_changed()
+        .expect("fake HTTP client should work");
+    assert_eq!(datafile.map(|datafile| datafile.revision()), Some(REVISION + 1));
+}
@@ -0,0 +1,47 @@
+// External imports
+use std::sync::Arc;
+use std::thread;
+
+// Imports from Optimizely crate
+use optimizely::{client::UserContext, datafile::Datafile, Client};
+
+// Relative imports of sub modules
+use common::setup;
+mod common;
+
+// Only compiles if the type can be shared between threads
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn types_are_send_and_sync() {
+    assert_send_sync::<Client>();
+    assert_send_sync::<Datafile>();
+    assert_send_sync::<UserContext>();
+}
+
+#[test]
+fn client_shared_between_threads() {
+    let ctx = setup();
+    let client = Arc::new(ctx.client);
+
+    // Make decisions for different users on multiple threads
+    let handles = (0..4)
+        .map(|thread_index| {
+            let client = Arc::clone(&client);
+            thread::spawn(move || {
+                for i in 0..8 {
+                    let user_id = format!("user{}", thread_index * 8 + i);
+                    let user_context = client.create_user_context(&user_id);
+                    let _decision = user_context.decide("buy_button");
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().expect("thread should not panic");
+    }
+
+    // Each of those 32 users should dispatch an event
+    assert_eq!(ctx.decisions.lock().unwrap().len(), 32);
+}
@@ -1,8 +1,15 @@
+// External imports
+use error_stack::{report, Result};
+use std::sync::Arc;
+use std::thread;
+
 // Imports from Optimizely crate
-use optimizely::user_attributes;
+use optimizely::client::{OwnedUserContext, TrackError, UserContext};
+use optimizely::event_api::{DispatchError, EventDispatcher};
+use optimizely::{user_attributes, Client, Conversion, Decision};
 
 // Relative imports of sub modules
-use common::setup;
+use common::{setup, FILE_PATH};
 mod common;
 
 #[test]
@@ -40,7 +47,6 @@ fn user_context_with_attributes() {
 }
 
 #[test]
-#[cfg(feature = "online")]
 fn user_context_track_event() {
     let ctx = setup();
 
@@ -51,5 +57,84 @@ fn user_context_track_event() {
     user_context.track_event("purchase");
 
     // Assert that exactly one event is dispatched
-    assert_eq!(ctx.conversions.borrow().len(), 1);
+    assert_eq!(ctx.conversions.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn user_context_try_track_event_with_unknown_key() {
+    let ctx = setup();
+
+    // Create user context
+    let user_context = ctx.client.create_user_context("user123");
+
+    // An event key with a typo is reported
+    let report = user_context.try_track_event("purchsae").unwrap_err();
+    assert_eq!(report.current_context(), &TrackError::UnknownEventKey);
+
+    // No event is dispatched
+    assert_eq!(ctx.conversions.lock().unwrap().len(), 0);
+}
+
+// Event dispatcher that never has room for another event
+struct FullEventDispatcher;
+
+impl EventDispatcher for FullEventDispatcher {
+    fn send_conversion_event(&self, _user_context: &UserContext, _conversion: Conversion) -> Result<(), DispatchError> {
+        Err(report!(DispatchError::QueueFull))
+    }
+    fn send_decision_event(&self, _user_context: &UserContext, _decision: Decision) -> Result<(), DispatchError> {
+        Err(report!(DispatchError::QueueFull))
+    }
+}
+
+#[test]
+fn user_context_try_track_event_with_full_queue() {
+    let client = Client::from_local_datafile(FILE_PATH)
+        .expect("local datafile should work")
+        .with_event_dispatcher(FullEventDispatcher)
+        .initialize();
+
+    // Create user context
+    let user_context = client.create_user_context("user123");
+
+    // The error of the dispatcher is reported
+    let report = user_context.try_track_event("purchase").unwrap_err();
+    assert_eq!(report.current_context(), &TrackError::DispatcherQueueFull);
+}
+
+#[test]
+fn owned_user_context() {
+    let ctx = setup();
+    let client = Arc::new(ctx.client);
+
+    // Return an owned user context from a function
+    let create = |user_id: String| -> OwnedUserContext { client.create_owned_user_context(user_id) };
+    let user_context = create(String::from("user0"));
+
+    // Move the user context into another thread
+    let decision = thread::spawn(move || user_context.decide("buy_button"))
+        .join()
+        .expect("thread should not panic");
+
+    // Decision should be equal to the borrowed user context
+    assert_eq!(decision.variation_key(), "primary");
+    assert_eq!(ctx.decisions.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn owned_user_context_with_attributes() {
+    let ctx = setup();
+    let client = Arc::new(ctx.client);
+
+    // Create owned user context with given attributes
+    let user_context = client.create_owned_user_context_with_attributes(
+        "user123",
+        user_attributes! {
+            "is_employee" => "true",
+        },
+    );
+
+    // Attributes and user id should be equal to expected
+    assert_eq!(user_context.user_id(), "user123");
+    assert_eq!(user_context.attributes().get("is_employee").unwrap(), "true");
 }